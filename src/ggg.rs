@@ -1,19 +1,118 @@
 //! Common types for GDEF, GPOS and GSUB tables.
 
-use crate::GlyphId;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{GlyphId, NormalizedCoordinate, Tag};
 use crate::parser::*;
 
 
+/// An inclusive range of glyph IDs.
+///
+/// Can be used to parse glyph range records of tables that are not supported by this crate.
+/// A range with `start` greater than `end` is empty.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GlyphRange {
+    /// The first glyph ID in the range.
+    pub start: GlyphId,
+    /// The last glyph ID in the range.
+    pub end: GlyphId,
+}
+
+impl GlyphRange {
+    /// Checks that the range contains a glyph.
+    #[inline]
+    pub fn contains(&self, glyph_id: GlyphId) -> bool {
+        self.start <= glyph_id && glyph_id <= self.end
+    }
+
+    /// Returns the number of glyphs in the range.
+    ///
+    /// Returns `u32`, since the `0..=65535` range has 65536 glyphs.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        if self.is_empty() {
+            0
+        } else {
+            u32::from(self.end.0 - self.start.0) + 1
+        }
+    }
+
+    /// Checks that the range has no glyphs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    /// Returns an iterator over glyphs in the range.
+    #[inline]
+    pub fn iter(&self) -> GlyphRangeIter {
+        GlyphRangeIter {
+            next: if self.is_empty() { None } else { Some(self.start.0) },
+            end: self.end.0,
+        }
+    }
+}
+
+impl IntoIterator for GlyphRange {
+    type Item = GlyphId;
+    type IntoIter = GlyphRangeIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromData for GlyphRange {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(GlyphRange {
+            start: s.read::<GlyphId>()?,
+            end: s.read::<GlyphId>()?,
+        })
+    }
+}
+
+
+/// An iterator over glyphs in a `GlyphRange`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct GlyphRangeIter {
+    next: Option<u16>,
+    end: u16,
+}
+
+impl Iterator for GlyphRangeIter {
+    type Item = GlyphId;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.next?;
+        // `end` can be 0xFFFF, so we cannot simply check for `next > end`.
+        self.next = if id < self.end { Some(id + 1) } else { None };
+        Some(GlyphId(id))
+    }
+}
+
+
 #[derive(Clone, Copy)]
-struct RangeRecord {
-    start_glyph_id: GlyphId,
-    end_glyph_id: GlyphId,
+pub(crate) struct RangeRecord {
+    range: GlyphRange,
     value: u16,
 }
 
 impl RangeRecord {
-    fn range(&self) -> core::ops::RangeInclusive<GlyphId> {
-        self.start_glyph_id..=self.end_glyph_id
+    /// Finds a range that contains the glyph.
+    ///
+    /// Ranges are sorted by the start glyph ID and do not overlap.
+    fn find(records: LazyArray16<RangeRecord>, glyph_id: GlyphId) -> Option<Self> {
+        let index = records.partition_point(|r| r.range.end < glyph_id);
+        records.get(index).filter(|r| r.range.contains(glyph_id))
     }
 }
 
@@ -24,8 +123,7 @@ impl FromData for RangeRecord {
     fn parse(data: &[u8]) -> Option<Self> {
         let mut s = Stream::new(data);
         Some(RangeRecord {
-            start_glyph_id: s.read::<GlyphId>()?,
-            end_glyph_id: s.read::<GlyphId>()?,
+            range: s.read::<GlyphRange>()?,
             value: s.read::<u16>()?,
         })
     }
@@ -33,33 +131,228 @@ impl FromData for RangeRecord {
 
 
 /// A [Coverage Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#coverage-table).
+///
+/// Can be used to parse coverages of subtables that are not supported by this crate.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{CoverageTable, GlyphId};
+///
+/// let data = &[
+///     0x00, 0x01, // format: 1
+///     0x00, 0x02, // count: 2
+///     0x00, 0x05, // glyph [0]: 5
+///     0x00, 0x07, // glyph [1]: 7
+/// ];
+///
+/// let coverage = CoverageTable::new(data);
+/// assert!(coverage.contains(GlyphId(7)));
+/// assert!(!coverage.contains(GlyphId(6)));
+/// assert_eq!(coverage.get(GlyphId(7)), Some(1));
+/// assert_eq!(coverage.glyphs().collect::<Vec<_>>(), vec![GlyphId(5), GlyphId(7)]);
+/// ```
 #[derive(Clone, Copy, Debug)]
-pub(crate) struct CoverageTable<'a> {
+pub struct CoverageTable<'a> {
     data: &'a [u8],
 }
 
 impl<'a> CoverageTable<'a> {
+    /// Creates a new Coverage Table from raw data.
+    ///
+    /// The data is not validated. A malformed table simply does not cover any glyphs.
+    #[inline]
     pub fn new(data: &'a [u8]) -> Self {
         CoverageTable { data }
     }
 
+    /// Checks that a glyph is covered.
+    #[inline]
     pub fn contains(&self, glyph_id: GlyphId) -> bool {
+        self.get(glyph_id).is_some()
+    }
+
+    /// Returns the coverage index of a glyph.
+    ///
+    /// For format 1 this is the position in the glyph array.
+    /// For format 2 this is the range's start coverage index plus the glyph's offset in the range.
+    pub fn get(&self, glyph_id: GlyphId) -> Option<u16> {
         let mut s = Stream::new(self.data);
-        let format: u16 = try_opt_or!(s.read(), false);
+        let format: u16 = s.read()?;
+        match format {
+            1 => {
+                let count: u16 = s.read()?;
+                s.read_array16::<GlyphId>(count)?.binary_search(&glyph_id).map(|p| p.0)
+            }
+            2 => {
+                let count: u16 = s.read()?;
+                let records = s.read_array16::<RangeRecord>(count)?;
+                let record = RangeRecord::find(records, glyph_id)?;
+                // `value` is a start coverage index for the current range.
+                record.value.checked_add(glyph_id.0 - record.range.start.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over all covered glyphs in the coverage order.
+    ///
+    /// The n-th yielded glyph has the coverage index n.
+    pub fn glyphs(&self) -> CoverageGlyphs<'a> {
+        CoverageGlyphs(self.glyphs_impl().unwrap_or(CoverageGlyphsInner::Empty))
+    }
 
+    fn glyphs_impl(&self) -> Option<CoverageGlyphsInner<'a>> {
+        let mut s = Stream::new(self.data);
+        let format: u16 = s.read()?;
+        let count: u16 = s.read()?;
         match format {
             1 => {
-                let count = try_opt_or!(s.read::<u16>(), false);
-                s.read_array16::<GlyphId>(count).unwrap().binary_search(&glyph_id).is_some()
+                let glyphs = s.read_array16::<GlyphId>(count)?;
+                Some(CoverageGlyphsInner::Glyphs(glyphs.into_iter()))
             }
             2 => {
-                let count = try_opt_or!(s.read::<u16>(), false);
-                let records = try_opt_or!(s.read_array16::<RangeRecord>(count), false);
-                records.into_iter().any(|r| r.range().contains(&glyph_id))
+                let records = s.read_array16::<RangeRecord>(count)?;
+                Some(CoverageGlyphsInner::Ranges {
+                    records: records.into_iter(),
+                    range: GlyphRangeIter::default(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+
+/// An iterator over glyphs in a Coverage Table.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct CoverageGlyphs<'a>(CoverageGlyphsInner<'a>);
+
+impl Default for CoverageGlyphs<'_> {
+    #[inline]
+    fn default() -> Self {
+        CoverageGlyphs(CoverageGlyphsInner::Empty)
+    }
+}
+
+impl<'a> Iterator for CoverageGlyphs<'a> {
+    type Item = GlyphId;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum CoverageGlyphsInner<'a> {
+    Glyphs(LazyArrayIter16<'a, GlyphId>),
+    Ranges {
+        records: LazyArrayIter16<'a, RangeRecord>,
+        range: GlyphRangeIter,
+    },
+    Empty,
+}
+
+impl<'a> Iterator for CoverageGlyphsInner<'a> {
+    type Item = GlyphId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            CoverageGlyphsInner::Glyphs(ref mut glyphs) => glyphs.next(),
+            CoverageGlyphsInner::Ranges { ref mut records, ref mut range } => {
+                loop {
+                    if let Some(glyph_id) = range.next() {
+                        return Some(glyph_id);
+                    }
+
+                    *range = records.next()?.range.iter();
+                }
+            }
+            CoverageGlyphsInner::Empty => None,
+        }
+    }
+}
+
+
+/// A lookup subtable kind, which defines where coverage tables are stored.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum SubtableKind {
+    /// A coverage offset is stored right after the format.
+    Simple,
+    /// A Sequence Context subtable.
+    Context,
+    /// A Chained Sequence Context subtable.
+    ChainContext,
+}
+
+/// Checks that any coverage table of a lookup subtable contains a glyph.
+///
+/// For most subtables only the first coverage table is checked.
+/// Sequence context subtables of format 3 have multiple coverage tables,
+/// so all of them are checked.
+pub(crate) fn subtable_coverage_contains(
+    data: &[u8],
+    kind: SubtableKind,
+    glyph_id: GlyphId,
+) -> bool {
+    subtable_coverage_contains_impl(data, kind, glyph_id).unwrap_or(false)
+}
+
+fn subtable_coverage_contains_impl(
+    data: &[u8],
+    kind: SubtableKind,
+    glyph_id: GlyphId,
+) -> Option<bool> {
+    let mut s = Stream::new(data);
+    let format: u16 = s.read()?;
+    if format == 3 {
+        match kind {
+            SubtableKind::Simple => {}
+            SubtableKind::Context => {
+                let count: u16 = s.read()?;
+                s.skip::<u16>(); // seqLookupCount
+                let offsets = s.read_offsets16::<Offset16>(count, data)?;
+                return Some(coverages_contain(offsets, glyph_id));
+            }
+            SubtableKind::ChainContext => {
+                // Backtrack, input and lookahead coverages.
+                for _ in 0..3 {
+                    let count: u16 = s.read()?;
+                    let offsets = s.read_offsets16::<Offset16>(count, data)?;
+                    if coverages_contain(offsets, glyph_id) {
+                        return Some(true);
+                    }
+                }
+
+                return Some(false);
             }
-            _ => false,
         }
     }
+
+    let offset: Offset16 = s.read()?;
+    Some(CoverageTable::new(data.get(offset.to_usize()..)?).contains(glyph_id))
+}
+
+/// Checks that any of the coverage tables contains a glyph.
+pub(crate) fn coverages_contain(offsets: Offsets16<Offset16>, glyph_id: GlyphId) -> bool {
+    offsets.into_iter().any(|data| CoverageTable::new(data).contains(glyph_id))
+}
+
+/// Resolves an Extension Substitution/Positioning subtable.
+///
+/// Returns the actual lookup type and subtable data.
+pub(crate) fn resolve_extension(data: &[u8]) -> Option<(u16, &[u8])> {
+    let mut s = Stream::new(data);
+    let format: u16 = s.read()?;
+    if format != 1 {
+        return None;
+    }
+
+    let lookup_type: u16 = s.read()?;
+    let offset: Offset32 = s.read()?;
+    Some((lookup_type, data.get(offset.to_usize()..)?))
 }
 
 
@@ -79,16 +372,44 @@ impl FromData for Class {
 
 
 /// A [Class Definition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#class-definition-table).
-#[derive(Clone, Copy)]
-pub(crate) struct ClassDefinitionTable<'a> {
+///
+/// Can be used to parse classes of subtables that are not supported by this crate.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{ClassDefinitionTable, Class, GlyphId};
+///
+/// let data = &[
+///     0x00, 0x02, // format: 2
+///     0x00, 0x01, // count: 1
+///     0x00, 0x05, // start glyph [0]: 5
+///     0x00, 0x06, // end glyph [0]: 6
+///     0x00, 0x03, // class [0]: 3
+/// ];
+///
+/// let classes = ClassDefinitionTable::new(data);
+/// assert_eq!(classes.get(GlyphId(6)), Class(3));
+/// assert_eq!(classes.get(GlyphId(7)), Class(0));
+/// assert_eq!(classes.iter().collect::<Vec<_>>(),
+///            vec![(GlyphId(5), Class(3)), (GlyphId(6), Class(3))]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ClassDefinitionTable<'a> {
     data: &'a [u8],
 }
 
 impl<'a> ClassDefinitionTable<'a> {
+    /// Creates a new Class Definition Table from raw data.
+    ///
+    /// The data is not validated. A malformed table assigns Class 0 to all glyphs.
+    #[inline]
     pub fn new(data: &'a [u8]) -> Self {
         ClassDefinitionTable { data }
     }
 
+    /// Returns a glyph class.
+    ///
     /// Any glyph not included in the range of covered glyph IDs automatically belongs to Class 0.
     pub fn get(&self, glyph_id: GlyphId) -> Class {
         self.get_impl(glyph_id).unwrap_or(Class(0))
@@ -113,10 +434,2564 @@ impl<'a> ClassDefinitionTable<'a> {
             2 => {
                 let count: u16 = s.read()?;
                 let records = s.read_array16::<RangeRecord>(count)?;
-                records.into_iter().find(|r| r.range().contains(&glyph_id))
-                    .map(|record| Class(record.value))
+                RangeRecord::find(records, glyph_id).map(|record| Class(record.value))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over all glyphs with an explicitly assigned class.
+    ///
+    /// Glyphs are yielded in the table's stored order. Glyphs outside of the covered ranges
+    /// automatically belong to Class 0 and are not yielded.
+    pub fn iter(&self) -> ClassDefinitions<'a> {
+        ClassDefinitions(self.iter_impl().unwrap_or(ClassDefinitionsInner::Empty))
+    }
+
+    fn iter_impl(&self) -> Option<ClassDefinitionsInner<'a>> {
+        let mut s = Stream::new(self.data);
+        let format: u16 = s.read()?;
+        match format {
+            1 => {
+                let start_glyph_id: GlyphId = s.read()?;
+                let count: u16 = s.read()?;
+                let classes = s.read_array16::<Class>(count)?;
+                Some(ClassDefinitionsInner::Classes {
+                    next_glyph_id: Some(start_glyph_id.0),
+                    classes: classes.into_iter(),
+                })
+            }
+            2 => {
+                let count: u16 = s.read()?;
+                let records = s.read_array16::<RangeRecord>(count)?;
+                Some(ClassDefinitionsInner::Ranges {
+                    records: records.into_iter(),
+                    range: GlyphRangeIter::default(),
+                    class: Class(0),
+                })
             }
             _ => None,
         }
     }
 }
+
+
+/// An iterator over glyphs and their classes in a Class Definition Table.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct ClassDefinitions<'a>(ClassDefinitionsInner<'a>);
+
+impl Default for ClassDefinitions<'_> {
+    #[inline]
+    fn default() -> Self {
+        ClassDefinitions(ClassDefinitionsInner::Empty)
+    }
+}
+
+impl<'a> Iterator for ClassDefinitions<'a> {
+    type Item = (GlyphId, Class);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ClassDefinitionsInner<'a> {
+    Classes {
+        next_glyph_id: Option<u16>,
+        classes: LazyArrayIter16<'a, Class>,
+    },
+    Ranges {
+        records: LazyArrayIter16<'a, RangeRecord>,
+        // Glyphs and the class of the current range.
+        range: GlyphRangeIter,
+        class: Class,
+    },
+    Empty,
+}
+
+impl<'a> Iterator for ClassDefinitionsInner<'a> {
+    type Item = (GlyphId, Class);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            ClassDefinitionsInner::Classes { ref mut next_glyph_id, ref mut classes } => {
+                let glyph_id = (*next_glyph_id)?;
+                let class = classes.next()?;
+                *next_glyph_id = glyph_id.checked_add(1);
+                Some((GlyphId(glyph_id), class))
+            }
+            ClassDefinitionsInner::Ranges { ref mut records, ref mut range, ref mut class } => {
+                loop {
+                    if let Some(glyph_id) = range.next() {
+                        return Some((glyph_id, *class));
+                    }
+
+                    let record = records.next()?;
+                    *range = record.range.iter();
+                    *class = Class(record.value);
+                }
+            }
+            ClassDefinitionsInner::Empty => None,
+        }
+    }
+}
+
+
+/// An index in [Script List](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-list-table-and-script-record).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct ScriptIndex(pub u16);
+
+/// An index in [Language System Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#language-system-table).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct LanguageIndex(pub u16);
+
+/// An index in [Feature List](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#feature-list-table).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct FeatureIndex(pub u16);
+
+impl FromData for FeatureIndex {
+    const SIZE: usize = 2;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        u16::parse(data).map(FeatureIndex)
+    }
+}
+
+/// An index in [Lookup List](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#lookup-list-table).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct LookupIndex(pub u16);
+
+impl FromData for LookupIndex {
+    const SIZE: usize = 2;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        u16::parse(data).map(LookupIndex)
+    }
+}
+
+/// An index in [Feature Variations](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#featurevariations-table).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct FeatureVariationIndex(pub u32);
+
+
+/// A common interface for GSUB and GPOS tables.
+///
+/// Both tables share the same script, feature and lookup lists
+/// and differ only in lookup subtables.
+pub trait GlyphPosSubTable {
+    /// Returns a table version.
+    ///
+    /// `0x00010000` for 1.0 tables and `0x00010001` for 1.1 tables.
+    fn version(&self) -> u32;
+
+    /// Checks that the table has feature variations.
+    ///
+    /// Only version 1.1 tables with a non-null feature variations offset have them.
+    /// Malformed feature variations are treated as absent.
+    /// Unlike checking that `feature_variations` is empty, distinguishes
+    /// a table without feature variations from a table with zero records.
+    fn has_feature_variations(&self) -> bool;
+
+    /// Returns an iterator over scripts.
+    fn scripts(&self) -> Scripts<'_>;
+
+    /// Returns a script at `index`.
+    fn script_at(&self, index: ScriptIndex) -> Option<Script<'_>>;
+
+    /// Returns an iterator over features.
+    fn features(&self) -> Features<'_>;
+
+    /// Returns a feature at `index`.
+    fn feature_at(&self, index: FeatureIndex) -> Option<Feature<'_>>;
+
+    /// Returns an iterator over lookups.
+    fn lookups(&self) -> Lookups<'_>;
+
+    /// Returns a lookup at `index`.
+    fn lookup_at(&self, index: LookupIndex) -> Option<Lookup<'_>>;
+
+    /// Returns an iterator over feature variations.
+    fn feature_variations(&self) -> FeatureVariations<'_>;
+
+    /// Returns a feature variation at `index`.
+    fn feature_variation_at(&self, index: FeatureVariationIndex) -> Option<FeatureVariation<'_>>;
+
+    /// Returns the first feature variation whose conditions are satisfied
+    /// by the normalized variation coordinates.
+    ///
+    /// Per the OpenType spec, only the first matching record should be applied.
+    fn active_feature_variation(
+        &self,
+        coordinates: &[NormalizedCoordinate],
+    ) -> Option<FeatureVariation<'_>> {
+        self.feature_variations().find(|v| v.evaluate(coordinates))
+    }
+
+    /// Returns a feature at `index` with feature variations applied.
+    ///
+    /// If the first feature variation satisfied by the normalized variation coordinates
+    /// has an alternate for the feature at `index`, returns it.
+    /// Otherwise, returns the same feature as `feature_at`.
+    fn feature_at_variation(
+        &self,
+        index: FeatureIndex,
+        coordinates: &[NormalizedCoordinate],
+    ) -> Option<Feature<'_>> {
+        let substitution = self.active_feature_variation(coordinates).and_then(|variation| {
+            variation.substitutions().find(|substitution| substitution.index() == index)
+        });
+
+        match substitution {
+            Some(substitution) => Some(substitution.feature()),
+            None => self.feature_at(index),
+        }
+    }
+
+    /// Returns an iterator over lookups of the specified type.
+    ///
+    /// Extension lookups are resolved to the type of their subtables.
+    fn lookups_of_type(&self, lookup_type: u16) -> LookupsOfType<'_> {
+        LookupsOfType {
+            lookups: self.lookups(),
+            lookup_type,
+            index: 0,
+        }
+    }
+
+    /// Returns an index of a script with the specified tag.
+    fn script_index_by_tag(&self, tag: Tag) -> Option<ScriptIndex> {
+        self.scripts().script_by_tag(tag).map(|(index, _)| index)
+    }
+
+    /// Returns an iterator over indices of all features with the specified tag.
+    ///
+    /// Unlike `Features::feature_by_tag`, yields all matches, since the same tag is usually
+    /// used by multiple features (one per language system).
+    fn feature_indices_by_tag(&self, tag: Tag) -> FeatureIndicesByTag<'_> {
+        FeatureIndicesByTag {
+            records: self.features().list.records.into_iter(),
+            tag,
+            index: 0,
+        }
+    }
+
+    /// Returns a default language system of a script with the specified tag.
+    ///
+    /// Returns `None` when there is no such script or it doesn't have
+    /// a default language system.
+    fn default_language_for(&self, script: Tag) -> Option<Language<'_>> {
+        let (_, script) = self.scripts().script_by_tag(script)?;
+        script.default_language()
+    }
+
+    /// Returns lookups referenced by the language's features with the specified tags.
+    ///
+    /// Lookups are returned in the lookup list order, i.e. sorted by index
+    /// and without duplicates, no matter in which order features reference them.
+    /// This is the order in which lookups must be applied.
+    ///
+    /// The language's required feature is always included.
+    fn lookups_for_language<'a>(
+        &'a self,
+        language: Language<'a>,
+        features: &'a [Tag],
+    ) -> LanguageLookups<'a> {
+        LanguageLookups {
+            features: self.features(),
+            language,
+            feature_tags: features,
+            next: 0,
+            count: self.lookups().len(),
+        }
+    }
+}
+
+
+/// A [GSUB/GPOS common header](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub#gsub-header).
+#[derive(Clone, Copy)]
+pub(crate) struct GsubGposTable<'a> {
+    version: u32,
+    has_feature_variations: bool,
+    scripts: Scripts<'a>,
+    features: Features<'a>,
+    lookups: Lookups<'a>,
+    feature_variations: FeatureVariations<'a>,
+}
+
+impl<'a> GsubGposTable<'a> {
+    /// Same as `GlyphPosSubTable::features`, but not bound to `self`.
+    #[inline]
+    pub fn feature_list(&self) -> Features<'a> {
+        self.features
+    }
+
+    /// Same as `GlyphPosSubTable::lookups`, but not bound to `self`.
+    #[inline]
+    pub fn lookup_list(&self) -> Lookups<'a> {
+        self.lookups
+    }
+
+    /// `extension_lookup_type` is a type of the Extension lookup, which is table specific.
+    pub fn parse(data: &'a [u8], extension_lookup_type: u16) -> Option<Self> {
+        let mut s = Stream::new(data);
+
+        let major_version: u16 = s.read()?;
+        let minor_version: u16 = s.read()?;
+        if major_version != 1 {
+            return None;
+        }
+
+        let script_list_offset: Offset16 = s.read()?;
+        let feature_list_offset: Offset16 = s.read()?;
+        let lookup_list_offset: Offset16 = s.read()?;
+
+        let mut feature_variations_offset: Option<Offset32> = None;
+        if minor_version != 0 {
+            feature_variations_offset = s.read()?;
+        }
+
+        let scripts = Scripts::parse(data.get(script_list_offset.to_usize()..)?)?;
+        let features = Features::parse(data.get(feature_list_offset.to_usize()..)?)?;
+        let lookups = Lookups::parse(
+            data.get(lookup_list_offset.to_usize()..)?,
+            extension_lookup_type,
+        )?;
+
+        // Malformed feature variations are treated as absent,
+        // since the rest of the table is still usable.
+        let feature_variations = feature_variations_offset
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(|data| FeatureVariations::parse(data, features));
+
+        Some(GsubGposTable {
+            version: u32::from(major_version) << 16 | u32::from(minor_version),
+            has_feature_variations: feature_variations.is_some(),
+            scripts,
+            features,
+            lookups,
+            feature_variations: feature_variations.unwrap_or_default(),
+        })
+    }
+}
+
+impl<'a> GlyphPosSubTable for GsubGposTable<'a> {
+    #[inline]
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    #[inline]
+    fn has_feature_variations(&self) -> bool {
+        self.has_feature_variations
+    }
+
+    #[inline]
+    fn scripts(&self) -> Scripts<'_> {
+        self.scripts
+    }
+
+    #[inline]
+    fn script_at(&self, index: ScriptIndex) -> Option<Script<'_>> {
+        self.scripts.get(index)
+    }
+
+    #[inline]
+    fn features(&self) -> Features<'_> {
+        self.features
+    }
+
+    #[inline]
+    fn feature_at(&self, index: FeatureIndex) -> Option<Feature<'_>> {
+        self.features.get(index)
+    }
+
+    #[inline]
+    fn lookups(&self) -> Lookups<'_> {
+        self.lookups
+    }
+
+    #[inline]
+    fn lookup_at(&self, index: LookupIndex) -> Option<Lookup<'_>> {
+        self.lookups.get(index)
+    }
+
+    #[inline]
+    fn feature_variations(&self) -> FeatureVariations<'_> {
+        self.feature_variations
+    }
+
+    #[inline]
+    fn feature_variation_at(&self, index: FeatureVariationIndex) -> Option<FeatureVariation<'_>> {
+        self.feature_variations.get(index)
+    }
+}
+
+
+#[derive(Clone, Copy)]
+struct Record {
+    tag: Tag,
+    offset: Offset16,
+}
+
+impl FromData for Record {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(Record {
+            tag: s.read::<Tag>()?,
+            offset: s.read::<Offset16>()?,
+        })
+    }
+}
+
+
+/// A list of tagged records with offsets relative to the list's data.
+#[derive(Clone, Copy, Default)]
+struct RecordList<'a> {
+    data: &'a [u8],
+    records: LazyArray16<'a, Record>,
+}
+
+impl<'a> RecordList<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let count: u16 = s.read()?;
+        let records = s.read_array16::<Record>(count)?;
+        Some(RecordList { data, records })
+    }
+
+    #[inline]
+    fn len(&self) -> u16 {
+        self.records.len()
+    }
+
+    #[inline]
+    fn get(&self, index: u16) -> Option<(Tag, &'a [u8])> {
+        let record = self.records.get(index)?;
+        self.data.get(record.offset.to_usize()..).map(|data| (record.tag, data))
+    }
+}
+
+
+/// An iterator over GSUB/GPOS scripts.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct Scripts<'a> {
+    list: RecordList<'a>,
+    index: u16,
+}
+
+impl<'a> Scripts<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        Some(Scripts { list: RecordList::parse(data)?, index: 0 })
+    }
+
+    fn get(&self, index: ScriptIndex) -> Option<Script<'a>> {
+        let (tag, data) = self.list.get(index.0)?;
+        Script::parse(tag, data)
+    }
+
+    /// Returns a script by tag.
+    ///
+    /// Script records are sorted by tag, so a binary search is used.
+    #[inline]
+    pub fn script_by_tag(&self, tag: Tag) -> Option<(ScriptIndex, Script<'a>)> {
+        let (index, _) = self.list.records.binary_search_by_key(&tag, |r| r.tag)?;
+        let script = self.get(ScriptIndex(index))?;
+        Some((ScriptIndex(index), script))
+    }
+
+    /// Returns the total number of scripts, regardless of the iterator position.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.list.len()
+    }
+
+    /// Checks that there are no scripts.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Restarts the iteration from the first script.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.index = 0;
+    }
+}
+
+impl<'a> Iterator for Scripts<'a> {
+    type Item = Script<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.list.len() {
+            self.index += 1;
+            self.get(ScriptIndex(self.index - 1))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A malformed entry ends the iteration, so only the upper bound is known.
+        (0, Some(usize::from(self.list.len() - self.index)))
+    }
+}
+
+
+/// A [Script Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-table-and-language-system-record).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Script<'a> {
+    tag: Tag,
+    default_language: Option<Language<'a>>,
+    languages: RecordList<'a>,
+}
+
+impl<'a> Script<'a> {
+    fn parse(tag: Tag, data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let default_language_offset: Option<Offset16> = s.read()?;
+        let count: u16 = s.read()?;
+        let records = s.read_array16::<Record>(count)?;
+
+        let default_language = match default_language_offset {
+            Some(offset) => {
+                Some(Language::parse(Tag::from_bytes(b"dflt"), data.get(offset.to_usize()..)?)?)
+            }
+            None => None,
+        };
+
+        Some(Script {
+            tag,
+            default_language,
+            languages: RecordList { data, records },
+        })
+    }
+
+    /// Returns script's tag.
+    #[inline]
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns a default language system.
+    #[inline]
+    pub fn default_language(&self) -> Option<Language<'a>> {
+        self.default_language
+    }
+
+    /// Returns an iterator over language systems.
+    ///
+    /// The default language system is not included.
+    #[inline]
+    pub fn languages(&self) -> Languages<'a> {
+        Languages { list: self.languages, index: 0 }
+    }
+
+    /// Returns an iterator over language system tags and their indices.
+    ///
+    /// Unlike `languages`, doesn't parse language system tables, so it is much cheaper.
+    /// The default language system is not included.
+    #[inline]
+    pub fn language_tags(&self) -> LanguageTags<'a> {
+        LanguageTags { records: self.languages.records.into_iter(), index: 0 }
+    }
+
+    /// Returns a language system at `index`.
+    #[inline]
+    pub fn language_at(&self, index: LanguageIndex) -> Option<Language<'a>> {
+        let (tag, data) = self.languages.get(index.0)?;
+        Language::parse(tag, data)
+    }
+
+    /// Returns a language system by tag.
+    ///
+    /// Language system records are sorted by tag, so a binary search is used.
+    #[inline]
+    pub fn language_by_tag(&self, tag: Tag) -> Option<(LanguageIndex, Language<'a>)> {
+        let (index, _) = self.languages.records.binary_search_by_key(&tag, |r| r.tag)?;
+        let language = self.language_at(LanguageIndex(index))?;
+        Some((LanguageIndex(index), language))
+    }
+
+    /// Creates an owned copy of the script with all its language systems.
+    ///
+    /// Unlike `Script`, the copy doesn't borrow the font data, so it can outlive it.
+    /// But it requires allocations and copies all language systems upfront,
+    /// so prefer `Script` when the font data is still available.
+    ///
+    /// Malformed language systems are skipped.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn into_owned(self) -> OwnedScript {
+        OwnedScript {
+            tag: self.tag,
+            default_language: self.default_language.map(Language::into_owned),
+            languages: (0..self.languages.len())
+                .filter_map(|i| self.language_at(LanguageIndex(i)))
+                .map(Language::into_owned)
+                .collect(),
+        }
+    }
+}
+
+
+/// An iterator over script's language system tags and their indices.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct LanguageTags<'a> {
+    records: LazyArrayIter16<'a, Record>,
+    index: u16,
+}
+
+impl<'a> Iterator for LanguageTags<'a> {
+    type Item = (LanguageIndex, Tag);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.records.next()?;
+        self.index += 1;
+        Some((LanguageIndex(self.index - 1), record.tag))
+    }
+}
+
+
+/// An iterator over script's language systems.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct Languages<'a> {
+    list: RecordList<'a>,
+    index: u16,
+}
+
+impl<'a> Iterator for Languages<'a> {
+    type Item = Language<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.list.len() {
+            self.index += 1;
+            let (tag, data) = self.list.get(self.index - 1)?;
+            Language::parse(tag, data)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A malformed entry ends the iteration, so only the upper bound is known.
+        (0, Some(usize::from(self.list.len() - self.index)))
+    }
+}
+
+
+/// A [Language System Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#language-system-table).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Language<'a> {
+    tag: Tag,
+    required_feature_index: Option<FeatureIndex>,
+    feature_indices: LazyArray16<'a, FeatureIndex>,
+}
+
+impl<'a> Language<'a> {
+    fn parse(tag: Tag, data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        s.skip::<Offset16>(); // lookupOrderOffset
+        let required_feature_index: u16 = s.read()?;
+        let count: u16 = s.read()?;
+        let feature_indices = s.read_array16::<FeatureIndex>(count)?;
+
+        // 'If no required features are defined, then the requiredFeatureIndex is set to 0xFFFF.'
+        let required_feature_index = if required_feature_index != 0xFFFF {
+            Some(FeatureIndex(required_feature_index))
+        } else {
+            None
+        };
+
+        Some(Language {
+            tag,
+            required_feature_index,
+            feature_indices,
+        })
+    }
+
+    /// Returns language system's tag.
+    ///
+    /// The default language system has a `dflt` tag.
+    #[inline]
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns an index of a feature required by this language system.
+    #[inline]
+    pub fn required_feature_index(&self) -> Option<FeatureIndex> {
+        self.required_feature_index
+    }
+
+    /// Returns indices of features used by this language system.
+    ///
+    /// The required feature is not included.
+    #[inline]
+    pub fn feature_indices(&self) -> LazyArray16<'a, FeatureIndex> {
+        self.feature_indices
+    }
+
+    /// Returns indices of all features used by this language system.
+    ///
+    /// The required feature is yielded first and only once,
+    /// even when it is also listed in `feature_indices`.
+    #[inline]
+    pub fn all_feature_indices(&self) -> AllFeatureIndices<'a> {
+        AllFeatureIndices {
+            required: self.required_feature_index,
+            next_required: self.required_feature_index,
+            indices: self.feature_indices.into_iter(),
+        }
+    }
+
+    /// Creates an owned copy of the language system.
+    ///
+    /// Unlike `Language`, the copy doesn't borrow the font data, so it can outlive it.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn into_owned(self) -> OwnedLanguage {
+        OwnedLanguage {
+            tag: self.tag,
+            required_feature_index: self.required_feature_index,
+            feature_indices: self.feature_indices.into_iter().collect(),
+        }
+    }
+}
+
+
+/// An iterator over language system's feature indices, including the required one.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct AllFeatureIndices<'a> {
+    required: Option<FeatureIndex>,
+    next_required: Option<FeatureIndex>,
+    indices: LazyArrayIter16<'a, FeatureIndex>,
+}
+
+impl<'a> Iterator for AllFeatureIndices<'a> {
+    type Item = FeatureIndex;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(index) = self.next_required.take() {
+            return Some(index);
+        }
+
+        let required = self.required;
+        self.indices.find(|index| Some(*index) != required)
+    }
+}
+
+
+/// An iterator over GSUB/GPOS features.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct Features<'a> {
+    list: RecordList<'a>,
+    index: u16,
+}
+
+impl<'a> Features<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        Some(Features { list: RecordList::parse(data)?, index: 0 })
+    }
+
+    fn get(&self, index: FeatureIndex) -> Option<Feature<'a>> {
+        let (tag, data) = self.list.get(index.0)?;
+        Feature::parse(tag, data)
+    }
+
+    /// Returns the first feature with the specified tag.
+    ///
+    /// Unlike scripts and languages, feature records are not required to be sorted by tag
+    /// and the same tag can be used by multiple features (usually, one per language system).
+    /// So this is a linear search and only the first match is returned.
+    /// Use `Language::feature_indices` to find a feature for a specific language system.
+    #[inline]
+    pub fn feature_by_tag(&self, tag: Tag) -> Option<(FeatureIndex, Feature<'a>)> {
+        let index = self.list.records.into_iter().position(|r| r.tag == tag)?;
+        let index = FeatureIndex(index as u16);
+        let feature = self.get(index)?;
+        Some((index, feature))
+    }
+
+    /// Returns the total number of features, regardless of the iterator position.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.list.len()
+    }
+
+    /// Checks that there are no features.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Restarts the iteration from the first feature.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.index = 0;
+    }
+}
+
+impl<'a> Iterator for Features<'a> {
+    type Item = Feature<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.list.len() {
+            self.index += 1;
+            self.get(FeatureIndex(self.index - 1))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A malformed entry ends the iteration, so only the upper bound is known.
+        (0, Some(usize::from(self.list.len() - self.index)))
+    }
+}
+
+
+/// An iterator over indices of features with a specific tag.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct FeatureIndicesByTag<'a> {
+    records: LazyArrayIter16<'a, Record>,
+    tag: Tag,
+    index: u16,
+}
+
+impl<'a> Iterator for FeatureIndicesByTag<'a> {
+    type Item = FeatureIndex;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let record = self.records.next()?;
+            self.index += 1;
+            if record.tag == self.tag {
+                return Some(FeatureIndex(self.index - 1));
+            }
+        }
+    }
+}
+
+
+/// A [Feature Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#feature-table).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Feature<'a> {
+    tag: Tag,
+    data: &'a [u8],
+    params_offset: Option<Offset16>,
+    lookup_indices: LazyArray16<'a, LookupIndex>,
+}
+
+impl<'a> Feature<'a> {
+    fn parse(tag: Tag, data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let params_offset: Option<Offset16> = s.read()?;
+        let count: u16 = s.read()?;
+        let lookup_indices = s.read_array16::<LookupIndex>(count)?;
+        Some(Feature { tag, data, params_offset, lookup_indices })
+    }
+
+    /// Returns feature's tag.
+    ///
+    /// Alternate features from `FeatureSubstitution::feature` are not tagged in the font,
+    /// so they have the tag of the feature they replace instead of a null tag.
+    #[inline]
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns indices of lookups used by this feature.
+    ///
+    /// Indices are stored in the font order, which is not necessarily the application order.
+    #[inline]
+    pub fn lookup_indices(&self) -> LazyArray16<'a, LookupIndex> {
+        self.lookup_indices
+    }
+
+    /// Creates an owned copy of the feature.
+    ///
+    /// Unlike `Feature`, the copy doesn't borrow the font data, so it can outlive it.
+    /// Feature parameters are not copied.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn into_owned(self) -> OwnedFeature {
+        OwnedFeature {
+            tag: self.tag,
+            lookup_indices: self.lookup_indices.into_iter().collect(),
+        }
+    }
+
+    /// Returns raw feature-specific parameters.
+    ///
+    /// The data is not bounded, i.e. it contains everything past the parameters offset.
+    #[inline]
+    pub fn feature_params(&self) -> Option<&'a [u8]> {
+        self.data.get(self.params_offset?.to_usize()..)
+    }
+
+    /// Parses `size` feature parameters.
+    ///
+    /// Returns `None` for all other features.
+    pub fn size_params(&self) -> Option<SizeParams> {
+        if self.tag != Tag::from_bytes(b"size") {
+            return None;
+        }
+
+        let mut s = Stream::new(self.feature_params()?);
+        Some(SizeParams {
+            design_size: s.read::<u16>()?,
+            subfamily_id: s.read::<u16>()?,
+            subfamily_name_id: s.read::<u16>()?,
+            range_start: s.read::<u16>()?,
+            range_end: s.read::<u16>()?,
+        })
+    }
+
+    /// Parses character variant (`cv01`-`cv99`) feature parameters.
+    ///
+    /// Returns `None` for all other features.
+    pub fn char_variant_params(&self) -> Option<CharacterVariantParams<'a>> {
+        let tag = self.tag.to_bytes();
+        if !(tag[0] == b'c' && tag[1] == b'v' && tag[2].is_ascii_digit() && tag[3].is_ascii_digit()) {
+            return None;
+        }
+
+        let mut s = Stream::new(self.feature_params()?);
+        let format: u16 = s.read()?;
+        if format != 0 {
+            return None;
+        }
+
+        let feature_ui_label_name_id: u16 = s.read()?;
+        let feature_ui_tooltip_text_name_id: u16 = s.read()?;
+        let sample_text_name_id: u16 = s.read()?;
+        let named_parameters_count: u16 = s.read()?;
+        let first_param_ui_label_name_id: u16 = s.read()?;
+        let count: u16 = s.read()?;
+        let characters = s.read_array16::<U24>(count)?;
+        Some(CharacterVariantParams {
+            feature_ui_label_name_id,
+            feature_ui_tooltip_text_name_id,
+            sample_text_name_id,
+            named_parameters_count,
+            first_param_ui_label_name_id,
+            characters,
+        })
+    }
+}
+
+
+/// [`size` feature parameters](https://docs.microsoft.com/en-us/typography/opentype/spec/features_pt#tag-size).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SizeParams {
+    /// Design size in 720/inch units (decipoints).
+    pub design_size: u16,
+
+    /// A subfamily identifier.
+    ///
+    /// Zero when there is no recommended usage range.
+    pub subfamily_id: u16,
+
+    /// A subfamily name in the `name` table.
+    pub subfamily_name_id: u16,
+
+    /// Small end of the recommended usage range (exclusive) in decipoints.
+    pub range_start: u16,
+
+    /// Large end of the recommended usage range (inclusive) in decipoints.
+    pub range_end: u16,
+}
+
+
+/// [Character variant feature parameters](https://docs.microsoft.com/en-us/typography/opentype/spec/features_ae#tag-cv01--cv99).
+///
+/// Name IDs are set to zero when not present.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct CharacterVariantParams<'a> {
+    /// A feature name in the `name` table.
+    pub feature_ui_label_name_id: u16,
+
+    /// A feature tooltip in the `name` table.
+    pub feature_ui_tooltip_text_name_id: u16,
+
+    /// A sample text in the `name` table.
+    pub sample_text_name_id: u16,
+
+    /// The number of named parameters.
+    pub named_parameters_count: u16,
+
+    /// The first parameter name in the `name` table.
+    ///
+    /// Other parameter names use consecutive IDs.
+    pub first_param_ui_label_name_id: u16,
+
+    /// Unicode code points of characters for which this feature provides glyph variants.
+    pub characters: LazyArray16<'a, U24>,
+}
+
+
+/// An owned copy of a `Script`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct OwnedScript {
+    /// Script's tag.
+    pub tag: Tag,
+    /// A default language system.
+    pub default_language: Option<OwnedLanguage>,
+    /// Language systems, excluding the default one.
+    pub languages: Vec<OwnedLanguage>,
+}
+
+
+/// An owned copy of a `Language`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct OwnedLanguage {
+    /// Language system's tag.
+    pub tag: Tag,
+    /// An index of a feature required by this language system.
+    pub required_feature_index: Option<FeatureIndex>,
+    /// Indices of features used by this language system, excluding the required one.
+    pub feature_indices: Vec<FeatureIndex>,
+}
+
+
+/// An owned copy of a `Feature`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct OwnedFeature {
+    /// Feature's tag.
+    pub tag: Tag,
+    /// Indices of lookups used by this feature.
+    pub lookup_indices: Vec<LookupIndex>,
+}
+
+
+/// An iterator over GSUB/GPOS lookups.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct Lookups<'a> {
+    list: Offsets16<'a, Offset16>,
+    extension_lookup_type: u16,
+    index: u16,
+}
+
+impl<'a> Lookups<'a> {
+    fn parse(data: &'a [u8], extension_lookup_type: u16) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let count: u16 = s.read()?;
+        let list = s.read_offsets16(count, data)?;
+        Some(Lookups { list, extension_lookup_type, index: 0 })
+    }
+
+    pub(crate) fn get(&self, index: LookupIndex) -> Option<Lookup<'a>> {
+        Lookup::parse(self.list.get(index.0)?, self.extension_lookup_type)
+    }
+
+    /// Returns the total number of lookups, regardless of the iterator position.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.list.len()
+    }
+
+    /// Checks that there are no lookups.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Restarts the iteration from the first lookup.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.index = 0;
+    }
+}
+
+impl<'a> Iterator for Lookups<'a> {
+    type Item = Lookup<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.list.len() {
+            self.index += 1;
+            self.get(LookupIndex(self.index - 1))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A malformed entry ends the iteration, so only the upper bound is known.
+        (0, Some(usize::from(self.list.len() - self.index)))
+    }
+}
+
+
+/// A [Lookup Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#lookup-table).
+#[derive(Clone, Copy)]
+pub struct Lookup<'a> {
+    lookup_type: u16,
+    lookup_flag: u16,
+    offsets: Offsets16<'a, Offset16>,
+    mark_filtering_set: Option<u16>,
+    extension_lookup_type: u16,
+}
+
+impl<'a> Lookup<'a> {
+    fn parse(data: &'a [u8], extension_lookup_type: u16) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let lookup_type: u16 = s.read()?;
+        let lookup_flag: u16 = s.read()?;
+        let count: u16 = s.read()?;
+        let offsets = s.read_offsets16(count, data)?;
+
+        // `markFilteringSet` is present only when the `useMarkFilteringSet` flag is set.
+        let mark_filtering_set = if LookupFlags(lookup_flag).use_mark_filtering_set() {
+            Some(s.read::<u16>()?)
+        } else {
+            None
+        };
+
+        Some(Lookup {
+            lookup_type,
+            lookup_flag,
+            offsets,
+            mark_filtering_set,
+            extension_lookup_type,
+        })
+    }
+
+    /// Returns lookup's type.
+    ///
+    /// The meaning depends on the table. GSUB and GPOS have different lookup types.
+    #[inline]
+    pub fn lookup_type(&self) -> u16 {
+        self.lookup_type
+    }
+
+    /// Returns lookup flags.
+    #[inline]
+    pub fn flags(&self) -> LookupFlags {
+        LookupFlags(self.lookup_flag)
+    }
+
+    /// Returns an index of a mark glyph set in GDEF.
+    ///
+    /// Set only when `flags().use_mark_filtering_set()` is `true`.
+    #[inline]
+    pub fn mark_filtering_set(&self) -> Option<u16> {
+        self.mark_filtering_set
+    }
+
+    /// Returns the actual lookup type of a subtable.
+    ///
+    /// Extension lookups (type 7 in GSUB and type 9 in GPOS) wrap subtables
+    /// of other types. In this case, the type of the wrapped subtable is returned.
+    /// Otherwise, this is the same as `lookup_type`.
+    ///
+    /// `subtable` must be one of the `subtables()`.
+    #[inline]
+    pub fn resolved_type(&self, subtable: &[u8]) -> Option<u16> {
+        if self.lookup_type == self.extension_lookup_type {
+            resolve_extension(subtable).map(|(lookup_type, _)| lookup_type)
+        } else {
+            Some(self.lookup_type)
+        }
+    }
+
+    /// Returns an iterator over subtables' raw data.
+    ///
+    /// Null and out of bounds offsets are skipped.
+    /// It's up to the caller to parse a subtable according to `lookup_type`.
+    #[inline]
+    pub fn subtables(&self) -> OffsetsIter16<'a, Offset16> {
+        self.offsets.into_iter()
+    }
+
+    /// Returns the number of subtables, including the ones with null offsets.
+    #[inline]
+    pub fn subtable_count(&self) -> u16 {
+        self.offsets.len()
+    }
+}
+
+impl<'a> core::fmt::Debug for Lookup<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Lookup")
+            .field("lookup_type", &self.lookup_type)
+            .field("flags", &self.flags())
+            .field("subtables", &self.offsets.len())
+            .field("mark_filtering_set", &self.mark_filtering_set)
+            .finish()
+    }
+}
+
+
+/// [Lookup flags](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#lookupFlags).
+///
+/// Defines which glyphs should be skipped during matching.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct LookupFlags(pub u16);
+
+impl LookupFlags {
+    /// Checks that the cursive attachment should be applied right-to-left.
+    ///
+    /// Used only by GPOS Cursive Attachment lookups.
+    #[inline]
+    pub fn right_to_left(self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+
+    /// Checks that base glyphs should be skipped.
+    #[inline]
+    pub fn ignore_base_glyphs(self) -> bool {
+        self.0 & 0x0002 != 0
+    }
+
+    /// Checks that ligatures should be skipped.
+    #[inline]
+    pub fn ignore_ligatures(self) -> bool {
+        self.0 & 0x0004 != 0
+    }
+
+    /// Checks that combining marks should be skipped.
+    #[inline]
+    pub fn ignore_marks(self) -> bool {
+        self.0 & 0x0008 != 0
+    }
+
+    /// Checks that marks not listed in the lookup's mark filtering set should be skipped.
+    #[inline]
+    pub fn use_mark_filtering_set(self) -> bool {
+        self.0 & 0x0010 != 0
+    }
+
+    /// Returns a mark attachment class.
+    ///
+    /// When non-zero, marks of other attachment classes should be skipped.
+    #[inline]
+    pub fn mark_attachment_type(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+}
+
+
+/// An iterator over lookups of a specific type.
+///
+/// Created by [`GlyphPosSubTable::lookups_of_type`](trait.GlyphPosSubTable.html#method.lookups_of_type).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct LookupsOfType<'a> {
+    lookups: Lookups<'a>,
+    lookup_type: u16,
+    index: u16,
+}
+
+impl<'a> Iterator for LookupsOfType<'a> {
+    type Item = (LookupIndex, Lookup<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.lookups.len() {
+            let index = LookupIndex(self.index);
+            self.index += 1;
+
+            let lookup = match self.lookups.get(index) {
+                Some(lookup) => lookup,
+                None => continue,
+            };
+
+            // All subtables of an extension lookup must have the same type,
+            // so checking the first one is enough.
+            let lookup_type = if lookup.lookup_type == lookup.extension_lookup_type {
+                lookup.subtables().next().and_then(|data| lookup.resolved_type(data))
+            } else {
+                Some(lookup.lookup_type)
+            };
+
+            if lookup_type == Some(self.lookup_type) {
+                return Some((index, lookup));
+            }
+        }
+
+        None
+    }
+}
+
+
+/// An iterator over lookups used by all features with a specific tag.
+///
+/// Lookups are returned in the lookup list order and without duplicates.
+#[derive(Clone, Copy)]
+pub(crate) struct FeatureLookups<'a> {
+    features: Features<'a>,
+    tag: Tag,
+    // The smallest lookup index that wasn't checked yet.
+    next: u32,
+    count: u16,
+}
+
+impl<'a> FeatureLookups<'a> {
+    pub fn new(features: Features<'a>, lookups: Lookups<'a>, tag: Tag) -> Self {
+        FeatureLookups { features, tag, next: 0, count: lookups.len() }
+    }
+}
+
+impl Default for FeatureLookups<'_> {
+    #[inline]
+    fn default() -> Self {
+        FeatureLookups::new(Features::default(), Lookups::default(), Tag(0))
+    }
+}
+
+impl<'a> Iterator for FeatureLookups<'a> {
+    type Item = LookupIndex;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Same as `LanguageLookups`, but features are selected only by tag.
+        let mut first: Option<u16> = None;
+        for (index, record) in self.features.list.records.into_iter().enumerate() {
+            if record.tag != self.tag {
+                continue;
+            }
+
+            let feature = match self.features.get(FeatureIndex(index as u16)) {
+                Some(feature) => feature,
+                None => continue,
+            };
+
+            for index in feature.lookup_indices {
+                let index = index.0;
+                if u32::from(index) >= self.next && index < self.count
+                    && first.map(|first| index < first).unwrap_or(true)
+                {
+                    first = Some(index);
+                }
+            }
+        }
+
+        let index = first?;
+        self.next = u32::from(index) + 1;
+        Some(LookupIndex(index))
+    }
+}
+
+
+/// An iterator over lookups used by a language system.
+///
+/// Created by [`GlyphPosSubTable::lookups_for_language`](trait.GlyphPosSubTable.html#method.lookups_for_language).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct LanguageLookups<'a> {
+    features: Features<'a>,
+    language: Language<'a>,
+    feature_tags: &'a [Tag],
+    // The smallest lookup index that wasn't checked yet.
+    // `u32`, so it can be past the last possible index.
+    next: u32,
+    count: u16,
+}
+
+impl<'a> LanguageLookups<'a> {
+    /// Returns the smallest lookup index referenced by the language's features,
+    /// that is not smaller than `start`.
+    fn first_lookup_from(&self, start: u32) -> Option<u16> {
+        let mut first: Option<u16> = None;
+        let mut check_feature = |feature_index: FeatureIndex, required: bool| {
+            let feature = match self.features.get(feature_index) {
+                Some(feature) => feature,
+                None => return,
+            };
+
+            if !required && !self.feature_tags.contains(&feature.tag) {
+                return;
+            }
+
+            for index in feature.lookup_indices {
+                let index = index.0;
+                if u32::from(index) >= start && index < self.count
+                    && first.map(|first| index < first).unwrap_or(true)
+                {
+                    first = Some(index);
+                }
+            }
+        };
+
+        if let Some(feature_index) = self.language.required_feature_index {
+            check_feature(feature_index, true);
+        }
+
+        for feature_index in self.language.feature_indices {
+            check_feature(feature_index, false);
+        }
+
+        first
+    }
+}
+
+impl<'a> Iterator for LanguageLookups<'a> {
+    type Item = LookupIndex;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Instead of collecting lookups from features and sorting them,
+        // which would require an allocation, we are looking for the next smallest
+        // referenced lookup index on each step.
+        // This way, each step is a single pass over the features' lookup indices.
+        let index = self.first_lookup_from(self.next)?;
+        self.next = u32::from(index) + 1;
+        Some(LookupIndex(index))
+    }
+}
+
+
+#[derive(Clone, Copy)]
+struct FeatureVariationRecord {
+    condition_set_offset: Option<Offset32>,
+    substitutions_offset: Option<Offset32>,
+}
+
+impl FromData for FeatureVariationRecord {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(FeatureVariationRecord {
+            condition_set_offset: s.read::<Option<Offset32>>()?,
+            substitutions_offset: s.read::<Option<Offset32>>()?,
+        })
+    }
+}
+
+
+/// An iterator over GSUB/GPOS feature variations.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct FeatureVariations<'a> {
+    data: &'a [u8],
+    records: LazyArray32<'a, FeatureVariationRecord>,
+    features: Features<'a>,
+    index: u32,
+}
+
+impl<'a> FeatureVariations<'a> {
+    fn parse(data: &'a [u8], features: Features<'a>) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version: u16 = s.read()?;
+        s.skip::<u16>(); // minorVersion
+        if major_version != 1 {
+            return None;
+        }
+
+        let count: u32 = s.read()?;
+        let records = s.read_array32::<FeatureVariationRecord>(count)?;
+        Some(FeatureVariations { data, records, features, index: 0 })
+    }
+
+    fn get(&self, index: FeatureVariationIndex) -> Option<FeatureVariation<'a>> {
+        let record = self.records.get(index.0)?;
+
+        let conditions = match record.condition_set_offset {
+            Some(offset) => ConditionSet::parse(self.data.get(offset.to_usize()..)?)?,
+            None => ConditionSet::default(),
+        };
+
+        let substitutions = match record.substitutions_offset {
+            Some(offset) => {
+                let data = self.data.get(offset.to_usize()..)?;
+                FeatureSubstitutions::parse(data, self.features)?
+            }
+            None => FeatureSubstitutions::default(),
+        };
+
+        Some(FeatureVariation { conditions, substitutions })
+    }
+
+    /// Returns the total number of feature variations, regardless of the iterator position.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.records.len()
+    }
+
+    /// Checks that there are no feature variations.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Restarts the iteration from the first feature variation.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.index = 0;
+    }
+}
+
+impl<'a> Iterator for FeatureVariations<'a> {
+    type Item = FeatureVariation<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.records.len() {
+            self.index += 1;
+            self.get(FeatureVariationIndex(self.index - 1))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A malformed entry ends the iteration, so only the upper bound is known.
+        (0, Some(usize::num_from(self.records.len() - self.index)))
+    }
+}
+
+
+/// A [Feature Variation Record](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#featurevariations-table).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct FeatureVariation<'a> {
+    conditions: ConditionSet<'a>,
+    substitutions: FeatureSubstitutions<'a>,
+}
+
+impl<'a> FeatureVariation<'a> {
+    /// Checks that all conditions are satisfied by the normalized variation coordinates.
+    ///
+    /// Variations with no conditions are always satisfied.
+    /// Variations with an unknown condition are never satisfied.
+    ///
+    /// `coordinates` can be shorter than the number of axes referenced by conditions.
+    /// Missing coordinates are treated as default, aka `0`.
+    pub fn evaluate(&self, coordinates: &[NormalizedCoordinate]) -> bool {
+        let mut conditions = self.conditions;
+        conditions.all(|c| c.evaluate(coordinates))
+    }
+
+    /// Returns an iterator over indices of variation axes referenced by conditions.
+    ///
+    /// Indices are yielded in the conditions order and can have duplicates.
+    /// Unknown conditions are skipped.
+    #[inline]
+    pub fn axis_indices(&self) -> ConditionAxisIndices<'a> {
+        ConditionAxisIndices { conditions: self.conditions }
+    }
+
+    /// Returns an iterator over conditions.
+    #[inline]
+    pub fn conditions(&self) -> ConditionSet<'a> {
+        self.conditions
+    }
+
+    /// Returns an iterator over feature substitutions.
+    #[inline]
+    pub fn substitutions(&self) -> FeatureSubstitutions<'a> {
+        self.substitutions
+    }
+}
+
+
+/// An iterator over variation axis indices referenced by a `FeatureVariation`.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct ConditionAxisIndices<'a> {
+    conditions: ConditionSet<'a>,
+}
+
+impl<'a> Iterator for ConditionAxisIndices<'a> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.conditions.next()? {
+                Condition::Format1 { axis_index, .. } => return Some(axis_index),
+                Condition::Unknown => {}
+            }
+        }
+    }
+}
+
+
+/// An iterator over [Condition Set](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#conditionset-table) conditions.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct ConditionSet<'a> {
+    data: &'a [u8],
+    offsets: LazyArray16<'a, Offset32>,
+    index: u16,
+}
+
+impl<'a> ConditionSet<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let count: u16 = s.read()?;
+        let offsets = s.read_array16::<Offset32>(count)?;
+        Some(ConditionSet { data, offsets, index: 0 })
+    }
+}
+
+impl<'a> Iterator for ConditionSet<'a> {
+    type Item = Condition;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.offsets.len() {
+            return None;
+        }
+
+        self.index += 1;
+        // Malformed conditions are reported as unknown instead of ending the iteration,
+        // otherwise the whole condition set could be considered satisfied.
+        let condition = self.offsets.get(self.index - 1)
+            .and_then(|offset| Condition::parse(self.data.get(offset.to_usize()..)?))
+            .unwrap_or(Condition::Unknown);
+        Some(condition)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.offsets.len() - self.index);
+        (len, Some(len))
+    }
+}
+
+
+/// A [Condition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#condition-table).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Condition {
+    /// A condition table format 1: font variation axis range.
+    Format1 {
+        /// An index of a variation axis in `fvar`.
+        axis_index: u16,
+        /// A minimum normalized value.
+        filter_range_min: NormalizedCoordinate,
+        /// A maximum normalized value.
+        filter_range_max: NormalizedCoordinate,
+    },
+    /// A condition of an unknown format or a malformed condition.
+    Unknown,
+}
+
+impl Condition {
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format: u16 = s.read()?;
+        match format {
+            1 => Some(Condition::Format1 {
+                axis_index: s.read::<u16>()?,
+                filter_range_min: NormalizedCoordinate::from(s.read::<i16>()?),
+                filter_range_max: NormalizedCoordinate::from(s.read::<i16>()?),
+            }),
+            _ => Some(Condition::Unknown),
+        }
+    }
+
+    /// Checks that the axis coordinate is within the range.
+    ///
+    /// Missing coordinates are treated as default, aka `0`.
+    ///
+    /// Unknown conditions cannot be evaluated and are never satisfied.
+    pub fn evaluate(&self, coordinates: &[NormalizedCoordinate]) -> bool {
+        match *self {
+            Condition::Format1 { axis_index, filter_range_min, filter_range_max } => {
+                let coord = coordinates.get(usize::from(axis_index)).copied().unwrap_or_default();
+                filter_range_min.get() <= coord.get() && coord.get() <= filter_range_max.get()
+            }
+            Condition::Unknown => false,
+        }
+    }
+}
+
+
+#[derive(Clone, Copy)]
+struct FeatureSubstitutionRecord {
+    index: FeatureIndex,
+    offset: Offset32,
+}
+
+impl FromData for FeatureSubstitutionRecord {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(FeatureSubstitutionRecord {
+            index: s.read::<FeatureIndex>()?,
+            offset: s.read::<Offset32>()?,
+        })
+    }
+}
+
+
+/// An iterator over [Feature Table Substitution](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#featuretablesubstitution-table) records.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct FeatureSubstitutions<'a> {
+    data: &'a [u8],
+    records: LazyArray16<'a, FeatureSubstitutionRecord>,
+    features: Features<'a>,
+    index: u16,
+}
+
+impl<'a> FeatureSubstitutions<'a> {
+    fn parse(data: &'a [u8], features: Features<'a>) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version: u16 = s.read()?;
+        s.skip::<u16>(); // minorVersion
+        if major_version != 1 {
+            return None;
+        }
+
+        let count: u16 = s.read()?;
+        let records = s.read_array16::<FeatureSubstitutionRecord>(count)?;
+        Some(FeatureSubstitutions { data, records, features, index: 0 })
+    }
+}
+
+impl<'a> Iterator for FeatureSubstitutions<'a> {
+    type Item = FeatureSubstitution<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.records.len() {
+            return None;
+        }
+
+        self.index += 1;
+        let record = self.records.get(self.index - 1)?;
+        // Alternate feature tables are not tagged, so we have to use the original tag.
+        let (tag, _) = self.features.list.get(record.index.0)?;
+        let feature = Feature::parse(tag, self.data.get(record.offset.to_usize()..)?)?;
+        Some(FeatureSubstitution { index: record.index, feature })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A malformed entry ends the iteration, so only the upper bound is known.
+        (0, Some(usize::from(self.records.len() - self.index)))
+    }
+}
+
+
+/// A feature substitution.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct FeatureSubstitution<'a> {
+    index: FeatureIndex,
+    feature: Feature<'a>,
+}
+
+impl<'a> FeatureSubstitution<'a> {
+    /// Returns an index of a feature that should be replaced.
+    #[inline]
+    pub fn index(&self) -> FeatureIndex {
+        self.index
+    }
+
+    /// Returns an alternate feature.
+    ///
+    /// Alternate feature tables are not tagged, so the tag of the feature at `index` is used.
+    #[inline]
+    pub fn feature(&self) -> Feature<'a> {
+        self.feature
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coverage_index() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x03, // glyph count: 3
+            0x00, 0x02, // glyph [0]: 2
+            0x00, 0x05, // glyph [1]: 5
+            0x00, 0x09, // glyph [2]: 9
+        ];
+
+        let coverage = CoverageTable::new(data);
+        assert_eq!(coverage.get(GlyphId(2)), Some(0));
+        assert_eq!(coverage.get(GlyphId(9)), Some(2));
+        assert_eq!(coverage.get(GlyphId(3)), None);
+        assert!(coverage.contains(GlyphId(5)));
+        assert!(!coverage.contains(GlyphId(10)));
+
+        let data = &[
+            0x00, 0x02, // format: 2
+            0x00, 0x02, // range count: 2
+            0x00, 0x02, // start glyph ID [0]: 2
+            0x00, 0x04, // end glyph ID [0]: 4
+            0x00, 0x00, // start coverage index [0]: 0
+            0x00, 0x0A, // start glyph ID [1]: 10
+            0x00, 0x0B, // end glyph ID [1]: 11
+            0x00, 0x03, // start coverage index [1]: 3
+        ];
+
+        let coverage = CoverageTable::new(data);
+        assert_eq!(coverage.get(GlyphId(3)), Some(1));
+        assert_eq!(coverage.get(GlyphId(11)), Some(4));
+        assert_eq!(coverage.get(GlyphId(5)), None);
+        assert!(coverage.contains(GlyphId(10)));
+        assert!(!coverage.contains(GlyphId(1)));
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn owned_copies() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let (scripts, features) = {
+            let face = crate::Face::from_slice(&data, 0).unwrap();
+            let gsub = face.substitution_table().unwrap();
+            let scripts: Vec<_> = gsub.scripts().map(Script::into_owned).collect();
+            let features: Vec<_> = gsub.features().map(Feature::into_owned).collect();
+            (scripts, features)
+        };
+        // Owned copies outlive the font data.
+        drop(data);
+
+        assert_eq!(scripts.len(), 4);
+        let latn = &scripts[3];
+        assert_eq!(latn.tag, Tag::from_bytes(b"latn"));
+        assert_eq!(latn.default_language, Some(OwnedLanguage {
+            tag: Tag::from_bytes(b"dflt"),
+            required_feature_index: None,
+            feature_indices: vec![
+                FeatureIndex(3), FeatureIndex(12), FeatureIndex(21), FeatureIndex(35),
+            ],
+        }));
+        assert_eq!(latn.languages.len(), 5);
+        assert_eq!(latn.languages[1].tag, Tag::from_bytes(b"AZE "));
+
+        assert_eq!(features.len(), 41);
+        assert_eq!(features[9], OwnedFeature {
+            tag: Tag::from_bytes(b"frac"),
+            lookup_indices: vec![LookupIndex(2), LookupIndex(4)],
+        });
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn owned_script_skips_malformed_languages() {
+        let data = &[
+            0x00, 0x00, // default language system offset: NULL
+            0x00, 0x03, // language system count: 3
+            0x41, 0x41, 0x41, 0x20, // tag [0]: AAA
+            0x00, 0x16, // offset [0]: 22
+            0x42, 0x42, 0x42, 0x20, // tag [1]: BBB
+            0x00, 0xFF, // offset [1]: 255
+            0x43, 0x43, 0x43, 0x20, // tag [2]: CCC
+            0x00, 0x16, // offset [2]: 22
+            // Language system
+            0x00, 0x00, // lookup order offset: NULL
+            0xFF, 0xFF, // required feature index: none
+            0x00, 0x00, // feature index count: 0
+        ];
+
+        let script = Script::parse(Tag::from_bytes(b"latn"), data).unwrap();
+        assert_eq!(script.languages().count(), 1);
+
+        let tags: Vec<_> = script.into_owned().languages.iter().map(|l| l.tag).collect();
+        assert_eq!(tags, vec![Tag::from_bytes(b"AAA "), Tag::from_bytes(b"CCC ")]);
+    }
+
+    #[test]
+    fn glyph_range() {
+        let range = GlyphRange { start: GlyphId(2), end: GlyphId(4) };
+        assert!(range.contains(GlyphId(2)));
+        assert!(range.contains(GlyphId(4)));
+        assert!(!range.contains(GlyphId(5)));
+        assert_eq!(range.len(), 3);
+        assert_eq!(range.iter().collect::<std::vec::Vec<_>>(),
+                   vec![GlyphId(2), GlyphId(3), GlyphId(4)]);
+
+        let range = GlyphRange { start: GlyphId(65534), end: GlyphId(65535) };
+        assert_eq!(range.len(), 2);
+        assert_eq!(range.into_iter().collect::<std::vec::Vec<_>>(),
+                   vec![GlyphId(65534), GlyphId(65535)]);
+
+        assert_eq!(GlyphRange { start: GlyphId(0), end: GlyphId(65535) }.len(), 65536);
+
+        let range = GlyphRange { start: GlyphId(5), end: GlyphId(4) };
+        assert!(range.is_empty());
+        assert_eq!(range.len(), 0);
+        assert_eq!(range.iter().count(), 0);
+    }
+
+    #[test]
+    fn coverage_glyphs() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x02, // glyph count: 2
+            0x00, 0x02, // glyph [0]: 2
+            0x00, 0x05, // glyph [1]: 5
+        ];
+
+        let glyphs: std::vec::Vec<_> = CoverageTable::new(data).glyphs().collect();
+        assert_eq!(glyphs, vec![GlyphId(2), GlyphId(5)]);
+
+        let data = &[
+            0x00, 0x02, // format: 2
+            0x00, 0x03, // range count: 3
+            0x00, 0x02, // start glyph ID [0]: 2
+            0x00, 0x04, // end glyph ID [0]: 4
+            0x00, 0x00, // start coverage index [0]: 0
+            0x00, 0x0A, // start glyph ID [1]: 10
+            0x00, 0x0A, // end glyph ID [1]: 10
+            0x00, 0x03, // start coverage index [1]: 3
+            0xFF, 0xFE, // start glyph ID [2]: 65534
+            0xFF, 0xFF, // end glyph ID [2]: 65535
+            0x00, 0x04, // start coverage index [2]: 4
+        ];
+
+        let glyphs: std::vec::Vec<_> = CoverageTable::new(data).glyphs().collect();
+        assert_eq!(glyphs, vec![
+            GlyphId(2), GlyphId(3), GlyphId(4), GlyphId(10), GlyphId(65534), GlyphId(65535),
+        ]);
+
+        assert_eq!(CoverageTable::new(&[0x00, 0x03, 0x00, 0x00]).glyphs().count(), 0);
+    }
+
+    #[test]
+    fn class_definitions() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x05, // start glyph ID: 5
+            0x00, 0x03, // glyph count: 3
+            0x00, 0x02, // class [0]: 2
+            0x00, 0x00, // class [1]: 0
+            0x00, 0x01, // class [2]: 1
+        ];
+
+        let classes: std::vec::Vec<_> = ClassDefinitionTable::new(data).iter().collect();
+        assert_eq!(classes, vec![
+            (GlyphId(5), Class(2)), (GlyphId(6), Class(0)), (GlyphId(7), Class(1)),
+        ]);
+
+        let data = &[
+            0x00, 0x02, // format: 2
+            0x00, 0x02, // range count: 2
+            0x00, 0x0A, // start glyph ID [0]: 10
+            0x00, 0x0B, // end glyph ID [0]: 11
+            0x00, 0x03, // class [0]: 3
+            0x00, 0x02, // start glyph ID [1]: 2
+            0x00, 0x02, // end glyph ID [1]: 2
+            0x00, 0x01, // class [1]: 1
+        ];
+
+        let classes: std::vec::Vec<_> = ClassDefinitionTable::new(data).iter().collect();
+        assert_eq!(classes, vec![
+            (GlyphId(10), Class(3)), (GlyphId(11), Class(3)), (GlyphId(2), Class(1)),
+        ]);
+    }
+
+    // Two features that reference lookups out of order.
+    const GSUB_DATA: &[u8] = &[
+        0x00, 0x01, 0x00, 0x00, // version: 1.0
+        0x00, 0x0A, // script list offset: 10
+        0x00, 0x20, // feature list offset: 32
+        0x00, 0x3C, // lookup list offset: 60
+        // Script List
+        0x00, 0x01, // script count: 1
+        0x44, 0x46, 0x4C, 0x54, // script tag [0]: DFLT
+        0x00, 0x08, // script offset [0]: 8
+        // Script
+        0x00, 0x04, // default language offset: 4
+        0x00, 0x00, // language count: 0
+        // Default Language
+        0x00, 0x00, // lookup order offset: NULL
+        0xFF, 0xFF, // required feature index: none
+        0x00, 0x02, // feature index count: 2
+        0x00, 0x00, // feature index [0]: 0
+        0x00, 0x01, // feature index [1]: 1
+        // Feature List
+        0x00, 0x02, // feature count: 2
+        0x63, 0x63, 0x6D, 0x70, // feature tag [0]: ccmp
+        0x00, 0x0E, // feature offset [0]: 14
+        0x6C, 0x69, 0x67, 0x61, // feature tag [1]: liga
+        0x00, 0x16, // feature offset [1]: 22
+        // Feature [0]
+        0x00, 0x00, // feature params offset: NULL
+        0x00, 0x02, // lookup index count: 2
+        0x00, 0x02, // lookup index [0]: 2
+        0x00, 0x00, // lookup index [1]: 0
+        // Feature [1]
+        0x00, 0x00, // feature params offset: NULL
+        0x00, 0x01, // lookup index count: 1
+        0x00, 0x01, // lookup index [0]: 1
+        // Lookup List
+        0x00, 0x03, // lookup count: 3
+        0x00, 0x08, // lookup offset [0]: 8
+        0x00, 0x0E, // lookup offset [1]: 14
+        0x00, 0x14, // lookup offset [2]: 20
+        // Lookup [0]
+        0x00, 0x01, // lookup type: 1
+        0x00, 0x00, // lookup flag: 0
+        0x00, 0x00, // subtable count: 0
+        // Lookup [1]
+        0x00, 0x01, // lookup type: 1
+        0x00, 0x00, // lookup flag: 0
+        0x00, 0x00, // subtable count: 0
+        // Lookup [2]
+        0x00, 0x04, // lookup type: 4
+        0x00, 0x00, // lookup flag: 0
+        0x00, 0x00, // subtable count: 0
+    ];
+
+    fn lookups(table: &GsubGposTable, tags: &[&[u8; 4]]) -> std::vec::Vec<u16> {
+        let tags: std::vec::Vec<Tag> = tags.iter().map(|t| Tag::from_bytes(t)).collect();
+        let language = table.scripts().next().unwrap().default_language().unwrap();
+        table.lookups_for_language(language, &tags).map(|i| i.0).collect()
+    }
+
+    #[test]
+    fn lookups_for_language_are_sorted() {
+        let table = GsubGposTable::parse(GSUB_DATA, 7).unwrap();
+        assert_eq!(table.lookups().count(), 3);
+        assert_eq!(table.lookup_at(LookupIndex(2)).unwrap().lookup_type(), 4);
+        assert_eq!(lookups(&table, &[b"liga", b"ccmp"]), &[0, 1, 2]);
+        assert_eq!(lookups(&table, &[b"ccmp"]), &[0, 2]);
+        assert_eq!(lookups(&table, &[b"liga"]), &[1]);
+        assert_eq!(lookups(&table, &[b"kern"]), &[] as &[u16]);
+    }
+
+    #[test]
+    fn default_language_for() {
+        let table = GsubGposTable::parse(GSUB_DATA, 7).unwrap();
+        let language = table.default_language_for(Tag::from_bytes(b"DFLT")).unwrap();
+        assert_eq!(language.tag(), Tag::from_bytes(b"dflt"));
+        assert_eq!(language.feature_indices().len(), 2);
+        assert!(table.default_language_for(Tag::from_bytes(b"latn")).is_none());
+    }
+
+    #[test]
+    fn size_params() {
+        let data = &[
+            0x00, 0x04, // feature params offset: 4
+            0x00, 0x00, // lookup index count: 0
+            // Feature Params
+            0x00, 0x64, // design size: 100
+            0x00, 0x01, // subfamily identifier: 1
+            0x01, 0x00, // subfamily name ID: 256
+            0x00, 0x50, // range start: 80
+            0x00, 0x8C, // range end: 140
+        ];
+
+        let feature = Feature::parse(Tag::from_bytes(b"size"), data).unwrap();
+        assert_eq!(feature.feature_params().unwrap().len(), 10);
+        assert_eq!(feature.size_params(), Some(SizeParams {
+            design_size: 100,
+            subfamily_id: 1,
+            subfamily_name_id: 256,
+            range_start: 80,
+            range_end: 140,
+        }));
+        assert!(feature.char_variant_params().is_none());
+
+        let feature = Feature::parse(Tag::from_bytes(b"liga"), data).unwrap();
+        assert!(feature.feature_params().is_some());
+        assert!(feature.size_params().is_none());
+    }
+
+    #[test]
+    fn char_variant_params() {
+        let data = &[
+            0x00, 0x06, // feature params offset: 6
+            0x00, 0x01, // lookup index count: 1
+            0x00, 0x00, // lookup index [0]: 0
+            // Feature Params
+            0x00, 0x00, // format: 0
+            0x01, 0x00, // feature UI label name ID: 256
+            0x01, 0x01, // feature UI tooltip text name ID: 257
+            0x00, 0x00, // sample text name ID: none
+            0x00, 0x02, // number of named parameters: 2
+            0x01, 0x02, // first param UI label name ID: 258
+            0x00, 0x02, // char count: 2
+            0x00, 0x00, 0x61, // character [0]: a
+            0x01, 0xF6, 0x00, // character [1]: U+1F600
+        ];
+
+        let feature = Feature::parse(Tag::from_bytes(b"cv01"), data).unwrap();
+        let params = feature.char_variant_params().unwrap();
+        assert_eq!(params.feature_ui_label_name_id, 256);
+        assert_eq!(params.feature_ui_tooltip_text_name_id, 257);
+        assert_eq!(params.sample_text_name_id, 0);
+        assert_eq!(params.named_parameters_count, 2);
+        assert_eq!(params.first_param_ui_label_name_id, 258);
+        let characters: std::vec::Vec<_> = params.characters.into_iter().map(|c| c.0).collect();
+        assert_eq!(characters, &[0x61, 0x1F600]);
+
+        assert!(Feature::parse(Tag::from_bytes(b"cvxx"), data).unwrap().char_variant_params().is_none());
+
+        // No params.
+        let feature = Feature::parse(Tag::from_bytes(b"cv01"), &[0x00, 0x00, 0x00, 0x00]).unwrap();
+        assert!(feature.feature_params().is_none());
+        assert!(feature.char_variant_params().is_none());
+    }
+
+    #[test]
+    fn all_feature_indices() {
+        let data = &[
+            0x00, 0x00, // lookup order offset: NULL
+            0x00, 0x02, // required feature index: 2
+            0x00, 0x03, // feature index count: 3
+            0x00, 0x00, // feature index [0]: 0
+            0x00, 0x02, // feature index [1]: 2
+            0x00, 0x05, // feature index [2]: 5
+        ];
+
+        let language = Language::parse(Tag::from_bytes(b"dflt"), data).unwrap();
+        let indices: std::vec::Vec<_> = language.all_feature_indices().map(|i| i.0).collect();
+        assert_eq!(indices, &[2, 0, 5]);
+
+        let table = GsubGposTable::parse(GSUB_DATA, 7).unwrap();
+        let language = table.scripts().next().unwrap().default_language().unwrap();
+        let indices: std::vec::Vec<_> = language.all_feature_indices().map(|i| i.0).collect();
+        assert_eq!(indices, &[0, 1]);
+    }
+
+    #[test]
+    fn lookup_subtables() {
+        let data = &[
+            0x00, 0x01, // lookup type: 1
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x03, // subtable count: 3
+            0x00, 0x0C, // subtable offset [0]: 12
+            0x00, 0x00, // subtable offset [1]: NULL
+            0x00, 0x0E, // subtable offset [2]: 14
+            // Subtable [0]
+            0x00, 0x01, // format: 1
+            // Subtable [1]
+            0x00, 0x02, // format: 2
+        ];
+
+        let lookup = Lookup::parse(data, 7).unwrap();
+        assert_eq!(lookup.subtable_count(), 3);
+        let subtables: std::vec::Vec<&[u8]> = lookup.subtables().collect();
+        assert_eq!(subtables, &[&[0x00, 0x01, 0x00, 0x02][..], &[0x00, 0x02][..]]);
+    }
+
+    #[test]
+    fn lookup_without_mark_filtering_set() {
+        let data = &[
+            0x00, 0x01, // lookup type: 1
+            0x00, 0x08, // lookup flag: ignore marks
+            0x00, 0x00, // subtable count: 0
+        ];
+
+        let lookup = Lookup::parse(data, 7).unwrap();
+        assert_eq!(lookup.mark_filtering_set(), None);
+    }
+
+    #[test]
+    fn lookup_with_mark_filtering_set() {
+        let data = &[
+            0x00, 0x01, // lookup type: 1
+            0x00, 0x10, // lookup flag: use mark filtering set
+            0x00, 0x00, // subtable count: 0
+            0x00, 0x02, // mark filtering set: 2
+        ];
+
+        let lookup = Lookup::parse(data, 7).unwrap();
+        assert_eq!(lookup.mark_filtering_set(), Some(2));
+
+        // Missing mark filtering set.
+        assert!(Lookup::parse(&data[..6], 7).is_none());
+    }
+
+    #[test]
+    fn lookup_flags() {
+        let flags = LookupFlags(0x0319);
+        assert!(flags.right_to_left());
+        assert!(!flags.ignore_base_glyphs());
+        assert!(!flags.ignore_ligatures());
+        assert!(flags.ignore_marks());
+        assert!(flags.use_mark_filtering_set());
+        assert_eq!(flags.mark_attachment_type(), 3);
+        assert_eq!(LookupFlags::default().mark_attachment_type(), 0);
+    }
+
+    #[test]
+    fn extension_lookup_type() {
+        let data = &[
+            0x00, 0x07, // lookup type: 7
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x01, // subtable count: 1
+            0x00, 0x08, // subtable offset [0]: 8
+            // Extension
+            0x00, 0x01, // format: 1
+            0x00, 0x04, // extension lookup type: 4
+            0x00, 0x00, 0x00, 0x08, // extension offset: 8
+        ];
+
+        let lookup = Lookup::parse(data, 7).unwrap();
+        let subtable = lookup.subtables().next().unwrap();
+        assert_eq!(lookup.lookup_type(), 7);
+        assert_eq!(lookup.resolved_type(subtable), Some(4));
+
+        // Type 7 is not an extension in GPOS.
+        let lookup = Lookup::parse(data, 9).unwrap();
+        assert_eq!(lookup.resolved_type(subtable), Some(7));
+    }
+
+    #[test]
+    fn lookups_of_type() {
+        let table = GsubGposTable::parse(GSUB_DATA, 7).unwrap();
+        let indices = |t| table.lookups_of_type(t).map(|(i, _)| i.0).collect::<std::vec::Vec<_>>();
+        assert_eq!(indices(1), &[0, 1]);
+        assert_eq!(indices(4), &[2]);
+        assert!(indices(2).is_empty());
+
+        // Replace the lookup list.
+        let mut data = GSUB_DATA[..60].to_vec();
+        data.extend_from_slice(&[
+            // Lookup List
+            0x00, 0x03, // lookup count: 3
+            0x00, 0x08, // lookup offset [0]: 8
+            0x00, 0x0E, // lookup offset [1]: 14
+            0x00, 0x1E, // lookup offset [2]: 30
+            // Lookup [0]
+            0x00, 0x04, // lookup type: 4
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x00, // subtable count: 0
+            // Lookup [1]
+            0x00, 0x07, // lookup type: 7
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x01, // subtable count: 1
+            0x00, 0x08, // subtable offset [0]: 8
+            // Extension
+            0x00, 0x01, // format: 1
+            0x00, 0x04, // extension lookup type: 4
+            0x00, 0x00, 0x00, 0x08, // extension offset: 8
+            // Lookup [2]
+            0x00, 0x01, // lookup type: 1
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x00, // subtable count: 0
+        ]);
+
+        let table = GsubGposTable::parse(&data, 7).unwrap();
+        let mut iter = table.lookups_of_type(4);
+        let (index, lookup) = iter.next().unwrap();
+        assert_eq!((index, lookup.lookup_type()), (LookupIndex(0), 4));
+        let (index, lookup) = iter.next().unwrap();
+        assert_eq!((index, lookup.lookup_type()), (LookupIndex(1), 7));
+        assert!(iter.next().is_none());
+        assert_eq!(table.lookups_of_type(7).count(), 0);
+    }
+
+    #[test]
+    fn lookups_for_language_with_required_feature() {
+        let mut data = GSUB_DATA.to_vec();
+        data[24..26].copy_from_slice(&[0x00, 0x01]); // required feature index: 1
+        let table = GsubGposTable::parse(&data, 7).unwrap();
+        assert_eq!(lookups(&table, &[b"ccmp"]), &[0, 1, 2]);
+        assert_eq!(lookups(&table, &[]), &[1]);
+    }
+
+    #[test]
+    fn mixed_condition_set() {
+        let data = &[
+            0x00, 0x03, // condition count: 3
+            0x00, 0x00, 0x00, 0x0E, // condition offset [0]: 14
+            0x00, 0x00, 0x00, 0x16, // condition offset [1]: 22
+            0x00, 0x00, 0x00, 0x1A, // condition offset [2]: 26
+            // Condition [0]
+            0x00, 0x01, // format: 1
+            0x00, 0x00, // axis index: 0
+            0x20, 0x00, // filter range min: 0.5
+            0x40, 0x00, // filter range max: 1.0
+            // Condition [1]
+            0x00, 0x02, // format: 2
+            0x00, 0x00, // unknown data
+            // Condition [2]
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // axis index: 1
+            0xC0, 0x00, // filter range min: -1.0
+            0x40, 0x00, // filter range max: 1.0
+        ];
+
+        let conditions = ConditionSet::parse(data).unwrap();
+        assert_eq!(conditions.count(), 3);
+
+        let mut iter = conditions;
+        assert_eq!(iter.next(), Some(Condition::Format1 {
+            axis_index: 0,
+            filter_range_min: NormalizedCoordinate::from(0.5),
+            filter_range_max: NormalizedCoordinate::from(1.0),
+        }));
+        assert_eq!(iter.next(), Some(Condition::Unknown));
+        assert_eq!(iter.next(), Some(Condition::Format1 {
+            axis_index: 1,
+            filter_range_min: NormalizedCoordinate::from(-1.0),
+            filter_range_max: NormalizedCoordinate::from(1.0),
+        }));
+        assert_eq!(iter.next(), None);
+
+        // The first and the last conditions are satisfied, but the unknown one is not.
+        let coordinates = &[NormalizedCoordinate::from(0.75), NormalizedCoordinate::from(0.0)];
+        let variation = FeatureVariation {
+            conditions,
+            substitutions: FeatureSubstitutions::default(),
+        };
+        assert!(!variation.evaluate(coordinates));
+    }
+
+    #[test]
+    fn feature_substitution_tag() {
+        let feature_list = &[
+            0x00, 0x02, // count: 2
+            0x6C, 0x69, 0x67, 0x61, // tag [0]: liga
+            0x00, 0x0E, // offset [0]: 14
+            0x6B, 0x65, 0x72, 0x6E, // tag [1]: kern
+            0x00, 0x0E, // offset [1]: 14
+            // Feature
+            0x00, 0x00, // feature params offset: NULL
+            0x00, 0x00, // lookup index count: 0
+        ];
+
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x01, // count: 1
+            0x00, 0x01, // feature index [0]: 1
+            0x00, 0x00, 0x00, 0x0C, // alternate feature offset [0]: 12
+            // Alternate Feature
+            0x00, 0x00, // feature params offset: NULL
+            0x00, 0x01, // lookup index count: 1
+            0x00, 0x03, // lookup index [0]: 3
+        ];
+
+        let features = Features::parse(feature_list).unwrap();
+        let mut substitutions = FeatureSubstitutions::parse(data, features).unwrap();
+        assert_eq!(substitutions.size_hint(), (0, Some(1)));
+        let substitution = substitutions.next().unwrap();
+        assert_eq!(substitutions.size_hint(), (0, Some(0)));
+        assert_eq!(substitution.index(), FeatureIndex(1));
+        assert_eq!(substitution.feature().tag(), Tag::from_bytes(b"kern"));
+        assert!(!substitution.feature().tag().is_null());
+        assert_eq!(substitution.feature().lookup_indices().get(0), Some(LookupIndex(3)));
+    }
+
+    #[test]
+    fn version() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x01, // version: 1.1
+            0x00, 0x0E, // script list offset: 14
+            0x00, 0x0E, // feature list offset: 14
+            0x00, 0x0E, // lookup list offset: 14
+            0x00, 0x00, 0x00, 0x00, // feature variations offset: NULL
+            // Script/Feature/Lookup List
+            0x00, 0x00, // count: 0
+        ];
+
+        let table = GsubGposTable::parse(data, 7).unwrap();
+        assert_eq!(table.version(), 0x00010001);
+        assert!(!table.has_feature_variations());
+        assert!(table.feature_variations().is_empty());
+
+        let mut data = data.to_vec();
+        data[13] = 0xFF; // feature variations offset: 255 (out of bounds)
+        let table = GsubGposTable::parse(&data, 7).unwrap();
+        assert!(!table.has_feature_variations());
+        assert!(table.feature_variations().is_empty());
+
+        data[3] = 0; // version: 1.0
+        let table = GsubGposTable::parse(&data, 7).unwrap();
+        assert_eq!(table.version(), 0x00010000);
+        assert!(!table.has_feature_variations());
+    }
+
+    #[test]
+    fn feature_at_variation() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x01, // version: 1.1
+            0x00, 0x0E, // script list offset: 14
+            0x00, 0x10, // feature list offset: 16
+            0x00, 0x1E, // lookup list offset: 30
+            0x00, 0x00, 0x00, 0x20, // feature variations offset: 32
+            // Script List
+            0x00, 0x00, // count: 0
+            // Feature List
+            0x00, 0x01, // count: 1
+            0x6C, 0x69, 0x67, 0x61, // tag [0]: liga
+            0x00, 0x08, // offset [0]: 8
+            // Feature
+            0x00, 0x00, // feature params offset: NULL
+            0x00, 0x01, // lookup index count: 1
+            0x00, 0x00, // lookup index [0]: 0
+            // Lookup List
+            0x00, 0x00, // count: 0
+            // Feature Variations
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x00, 0x00, 0x01, // count: 1
+            0x00, 0x00, 0x00, 0x10, // condition set offset [0]: 16
+            0x00, 0x00, 0x00, 0x1E, // substitutions offset [0]: 30
+            // Condition Set
+            0x00, 0x01, // count: 1
+            0x00, 0x00, 0x00, 0x06, // condition offset [0]: 6
+            // Condition
+            0x00, 0x01, // format: 1
+            0x00, 0x00, // axis index: 0
+            0x20, 0x00, // filter range min: 0.5
+            0x40, 0x00, // filter range max: 1.0
+            // Feature Substitutions
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x01, // count: 1
+            0x00, 0x00, // feature index [0]: 0
+            0x00, 0x00, 0x00, 0x0C, // alternate feature offset [0]: 12
+            // Alternate Feature
+            0x00, 0x00, // feature params offset: NULL
+            0x00, 0x01, // lookup index count: 1
+            0x00, 0x01, // lookup index [0]: 1
+        ];
+
+        let table = GsubGposTable::parse(data, 7).unwrap();
+        let liga = Tag::from_bytes(b"liga");
+
+        // The condition is satisfied, so the alternate feature is used.
+        let coordinates = &[NormalizedCoordinate::from(0.75)];
+        let feature = table.feature_at_variation(FeatureIndex(0), coordinates).unwrap();
+        assert_eq!(feature.tag(), liga);
+        assert_eq!(feature.lookup_indices().get(0), Some(LookupIndex(1)));
+
+        // The condition is not satisfied, so the original feature is used.
+        let coordinates = &[NormalizedCoordinate::from(0.25)];
+        let feature = table.feature_at_variation(FeatureIndex(0), coordinates).unwrap();
+        assert_eq!(feature.tag(), liga);
+        assert_eq!(feature.lookup_indices().get(0), Some(LookupIndex(0)));
+
+        assert!(table.feature_at_variation(FeatureIndex(1), coordinates).is_none());
+    }
+
+    #[test]
+    fn feature_variation_missing_coordinates() {
+        let data = &[
+            0x00, 0x03, // count: 3
+            0x00, 0x00, 0x00, 0x0E, // condition offset [0]: 14
+            0x00, 0x00, 0x00, 0x16, // condition offset [1]: 22
+            0x00, 0x00, 0x00, 0x1E, // condition offset [2]: 30
+            // Condition [0]
+            0x00, 0x01, // format: 1
+            0x00, 0x00, // axis index: 0
+            0x20, 0x00, // filter range min: 0.5
+            0x40, 0x00, // filter range max: 1.0
+            // Condition [1]
+            0x00, 0x01, // format: 1
+            0x00, 0x03, // axis index: 3
+            0xE0, 0x00, // filter range min: -0.5
+            0x20, 0x00, // filter range max: 0.5
+            // Condition [2]
+            0x00, 0x02, // format: 2
+        ];
+
+        let variation = FeatureVariation {
+            conditions: ConditionSet::parse(data).unwrap(),
+            substitutions: FeatureSubstitutions::default(),
+        };
+        assert_eq!(variation.axis_indices().collect::<std::vec::Vec<_>>(), vec![0, 3]);
+        assert_eq!(variation.conditions().size_hint(), (3, Some(3)));
+        // The unknown condition is never satisfied.
+        assert!(!variation.evaluate(&[NormalizedCoordinate::from(0.75)]));
+
+        // Without the unknown condition.
+        let mut data = data.to_vec();
+        data[1] = 2;
+        let variation = FeatureVariation {
+            conditions: ConditionSet::parse(&data).unwrap(),
+            substitutions: FeatureSubstitutions::default(),
+        };
+        // Axis 3 is missing and treated as 0, which is within the range.
+        assert!(variation.evaluate(&[NormalizedCoordinate::from(0.75)]));
+        assert!(!variation.evaluate(&[NormalizedCoordinate::from(0.25)]));
+        assert!(!variation.evaluate(&[]));
+
+        // Axis 3 requires a non-default value now.
+        data[26..28].copy_from_slice(&[0x20, 0x00]); // filter range min: 0.5
+        data[28..30].copy_from_slice(&[0x40, 0x00]); // filter range max: 1.0
+        let variation = FeatureVariation {
+            conditions: ConditionSet::parse(&data).unwrap(),
+            substitutions: FeatureSubstitutions::default(),
+        };
+        assert!(!variation.evaluate(&[NormalizedCoordinate::from(0.75)]));
+    }
+
+    #[test]
+    fn active_feature_variation() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x01, // version: 1.1
+            0x00, 0x0E, // script list offset: 14
+            0x00, 0x10, // feature list offset: 16
+            0x00, 0x12, // lookup list offset: 18
+            0x00, 0x00, 0x00, 0x14, // feature variations offset: 20
+            // Script List
+            0x00, 0x00, // count: 0
+            // Feature List
+            0x00, 0x00, // count: 0
+            // Lookup List
+            0x00, 0x00, // count: 0
+            // Feature Variations
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x00, 0x00, 0x02, // count: 2
+            0x00, 0x00, 0x00, 0x18, // condition set offset [0]: 24
+            0x00, 0x00, 0x00, 0x00, // substitutions offset [0]: NULL
+            0x00, 0x00, 0x00, 0x26, // condition set offset [1]: 38
+            0x00, 0x00, 0x00, 0x00, // substitutions offset [1]: NULL
+            // Condition Set [0]
+            0x00, 0x01, // count: 1
+            0x00, 0x00, 0x00, 0x06, // condition offset [0]: 6
+            // Condition
+            0x00, 0x01, // format: 1
+            0x00, 0x00, // axis index: 0
+            0x20, 0x00, // filter range min: 0.5
+            0x40, 0x00, // filter range max: 1.0
+            // Condition Set [1]
+            0x00, 0x00, // count: 0
+        ];
+
+        let table = GsubGposTable::parse(data, 7).unwrap();
+        assert_eq!(table.version(), 0x00010001);
+        assert!(table.has_feature_variations());
+        assert_eq!(table.feature_variations().count(), 2);
+        assert_eq!(table.feature_variations().len(), 2);
+        assert!(table.scripts().is_empty());
+        assert!(table.features().is_empty());
+        assert!(table.lookups().is_empty());
+
+        // `len` doesn't depend on the iterator position.
+        let mut variations = table.feature_variations();
+        assert_eq!(variations.size_hint(), (0, Some(2)));
+        variations.next();
+        assert_eq!(variations.len(), 2);
+        assert_eq!(variations.size_hint(), (0, Some(1)));
+
+        // Both variations are satisfied, but the first one wins.
+        let coordinates = &[NormalizedCoordinate::from(0.75)];
+        let variation = table.active_feature_variation(coordinates).unwrap();
+        assert_eq!(variation.conditions().count(), 1);
+
+        let coordinates = &[NormalizedCoordinate::from(0.0)];
+        let variation = table.active_feature_variation(coordinates).unwrap();
+        assert_eq!(variation.conditions().count(), 0);
+    }
+}
@@ -36,6 +36,10 @@ A high-level, safe, zero-allocation TrueType font parser.
 #[macro_use]
 extern crate std;
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+
+use core::convert::TryFrom;
 use core::fmt;
 use core::num::NonZeroU16;
 
@@ -57,15 +61,22 @@ mod var_store;
 mod writer;
 
 use tables::*;
-use parser::{Stream, FromData, NumFrom, TryNumFrom, LazyArray16, Offset32, Offset};
+use parser::{Stream, FromData, NumFrom, TryNumFrom, LazyArray16, Offset32, Offset, LongDateTime};
 use parser::{i16_bound, f32_bound};
 use head::IndexToLocationFormat;
-pub use fvar::{VariationAxes, VariationAxis};
+use var_store::RegionScalars;
+pub use avar::{SegmentMaps, SegmentMap};
+pub use cpal::RgbaColor;
+pub use ebsc::BitmapScale;
+pub use fvar::{VariationAxes, VariationAxis, StatOrderedVariationAxes};
+pub use fvar::{NamedInstance, NamedInstances, NamedInstanceCoordinates};
 pub use gdef::GlyphClass;
+pub use hhea::CaretSlope;
 pub use ggg::*;
 pub use name::*;
 pub use os2::*;
-pub use tables::{cmap, kern};
+pub use stat::{AxisValue, AxisValues, AxisValueValues};
+pub use tables::{cmap, gpos, gsub, kern};
 
 
 /// A type-safe wrapper for glyph ID.
@@ -73,6 +84,21 @@ pub use tables::{cmap, kern};
 #[derive(Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Default, Debug)]
 pub struct GlyphId(pub u16);
 
+impl GlyphId {
+    /// Returns the glyph ID as `u16`.
+    #[inline]
+    pub const fn get(&self) -> u16 {
+        self.0
+    }
+}
+
+impl From<u16> for GlyphId {
+    #[inline]
+    fn from(n: u16) -> Self {
+        GlyphId(n)
+    }
+}
+
 impl FromData for GlyphId {
     const SIZE: usize = 2;
 
@@ -399,6 +425,88 @@ pub trait OutlineBuilder {
     ///
     /// End of a contour.
     fn close(&mut self);
+
+    /// Reports the orientation of the current contour.
+    ///
+    /// Called right before `close()`, but only by `Face::outline_glyph_with_orientation`,
+    /// since calculating the orientation has a cost.
+    ///
+    /// The orientation is calculated from the contour's signed area in font units,
+    /// where the Y axis points up. Control points are included in the area calculation.
+    /// Fonts usually use one orientation for outer contours and the opposite one for holes.
+    ///
+    /// Does nothing by default.
+    fn contour_orientation(&mut self, clockwise: bool) {
+        let _ = clockwise;
+    }
+}
+
+
+/// Calculates the orientation of each contour and reports it
+/// via `OutlineBuilder::contour_orientation`.
+struct OrientationBuilder<'a> {
+    builder: &'a mut dyn OutlineBuilder,
+    start_x: f32,
+    start_y: f32,
+    last_x: f32,
+    last_y: f32,
+    // A doubled signed area, calculated using the shoelace formula.
+    area: f32,
+}
+
+impl<'a> OrientationBuilder<'a> {
+    fn new(builder: &'a mut dyn OutlineBuilder) -> Self {
+        OrientationBuilder {
+            builder,
+            start_x: 0.0,
+            start_y: 0.0,
+            last_x: 0.0,
+            last_y: 0.0,
+            area: 0.0,
+        }
+    }
+
+    fn push_point(&mut self, x: f32, y: f32) {
+        self.area += self.last_x * y - x * self.last_y;
+        self.last_x = x;
+        self.last_y = y;
+    }
+}
+
+impl<'a> OutlineBuilder for OrientationBuilder<'a> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start_x = x;
+        self.start_y = y;
+        self.last_x = x;
+        self.last_y = y;
+        self.area = 0.0;
+        self.builder.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push_point(x, y);
+        self.builder.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.push_point(x1, y1);
+        self.push_point(x, y);
+        self.builder.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push_point(x1, y1);
+        self.push_point(x2, y2);
+        self.push_point(x, y);
+        self.builder.curve_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.push_point(self.start_x, self.start_y);
+        // A positive area indicates a counter-clockwise contour in a Y-up coordinate system.
+        self.builder.contour_orientation(self.area < 0.0);
+        self.builder.close();
+    }
 }
 
 
@@ -423,6 +531,7 @@ pub enum RasterImageFormat {
 /// A glyph's raster image.
 ///
 /// Note, that glyph metrics are in pixels and not in font units.
+/// Use `origin_in_font_units` to get the offsets in font units.
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct RasterGlyphImage<'a> {
     /// Horizontal offset.
@@ -451,6 +560,24 @@ pub struct RasterGlyphImage<'a> {
     pub data: &'a [u8],
 }
 
+impl<'a> RasterGlyphImage<'a> {
+    /// Returns horizontal and vertical offsets in font units.
+    ///
+    /// Calculated as `offset * units_per_em / pixels_per_em`.
+    /// `units_per_em` can be retrieved via `Face::units_per_em`.
+    ///
+    /// Returns `None` when `units_per_em` or `pixels_per_em` is zero.
+    #[inline]
+    pub fn origin_in_font_units(&self, units_per_em: u16) -> Option<(f32, f32)> {
+        if units_per_em == 0 || self.pixels_per_em == 0 {
+            return None;
+        }
+
+        let scale = f32::from(units_per_em) / f32::from(self.pixels_per_em);
+        Some((f32::from(self.x) * scale, f32::from(self.y) * scale))
+    }
+}
+
 
 /// A table name.
 #[repr(C)]
@@ -534,6 +661,28 @@ impl VarCoords {
 }
 
 
+/// Precomputed variation data for a fixed set of variation coordinates.
+///
+/// Evaluating Item Variation Store regions is the most expensive part
+/// of applying `HVAR`, `VVAR` and `CFF2` variations and it depends only on coordinates.
+/// So when processing many glyphs at the same variation instance,
+/// it's better to do it once.
+///
+/// Created by `Face::variation_instance_cache` and used by
+/// `Face::glyph_hor_advance_cached`, `Face::glyph_ver_advance_cached`,
+/// `Face::glyph_hor_side_bearing_cached`, `Face::glyph_ver_side_bearing_cached`
+/// and `Face::outline_glyph_cached`.
+/// A cache becomes stale after `Face::set_variation` and will be ignored.
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct VariationInstanceCache {
+    coordinates: VarCoords,
+    hvar: RegionScalars,
+    vvar: RegionScalars,
+    cff2: RegionScalars,
+}
+
+
 /// A list of font face parsing errors.
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum FaceParsingError {
@@ -586,9 +735,14 @@ pub struct Face<'a> {
     cff1: Option<cff1::Metadata<'a>>,
     cff2: Option<cff2::Metadata<'a>>,
     cmap: Option<cmap::Subtables<'a>>,
+    colr: Option<colr::Table<'a>>,
+    cpal: Option<cpal::Table<'a>>,
+    ebsc: Option<ebsc::Table<'a>>,
     fvar: Option<fvar::Table<'a>>,
     gdef: Option<gdef::Table<'a>>,
     glyf: Option<&'a [u8]>,
+    gpos: Option<gpos::Gpos<'a>>,
+    gsub: Option<gsub::Gsub<'a>>,
     gvar: Option<gvar::Table<'a>>,
     head: &'a [u8],
     hhea: &'a [u8],
@@ -603,6 +757,7 @@ pub struct Face<'a> {
     vhea: Option<&'a [u8]>,
     vmtx: Option<hmtx::Table<'a>>,
     sbix: Option<&'a [u8]>,
+    stat: Option<stat::Table<'a>>,
     svg_: Option<&'a [u8]>,
     vorg: Option<vorg::Table<'a>>,
     vvar: Option<hvar::Table<'a>>,
@@ -665,9 +820,14 @@ impl<'a> Face<'a> {
             cff1: None,
             cff2: None,
             cmap: None,
+            colr: None,
+            cpal: None,
+            ebsc: None,
             fvar: None,
             gdef: None,
             glyf: None,
+            gpos: None,
+            gsub: None,
             gvar: None,
             head: &[],
             hhea: &[],
@@ -682,6 +842,7 @@ impl<'a> Face<'a> {
             vhea: None,
             vmtx: None,
             sbix: None,
+            stat: None,
             svg_: None,
             vorg: None,
             vvar: None,
@@ -693,6 +854,7 @@ impl<'a> Face<'a> {
         let mut hmtx = None;
         let mut vmtx = None;
         let mut loca = None;
+        let mut gvar = None;
 
         for table in tables {
             let offset = usize::num_from(table.offset);
@@ -705,10 +867,16 @@ impl<'a> Face<'a> {
                 b"CBLC" => face.cblc = data.get(range),
                 b"CFF " => face.cff1 = data.get(range).and_then(|data| cff1::parse_metadata(data)),
                 b"CFF2" => face.cff2 = data.get(range).and_then(|data| cff2::parse_metadata(data)),
+                b"COLR" => face.colr = data.get(range).and_then(colr::Table::parse),
+                b"CPAL" => face.cpal = data.get(range).and_then(cpal::Table::parse),
+                b"EBSC" => face.ebsc = data.get(range).and_then(ebsc::Table::parse),
                 b"GDEF" => face.gdef = data.get(range).and_then(|data| gdef::Table::parse(data)),
+                b"GPOS" => face.gpos = data.get(range).and_then(gpos::Gpos::parse),
+                b"GSUB" => face.gsub = data.get(range).and_then(gsub::Gsub::parse),
                 b"HVAR" => face.hvar = data.get(range).and_then(|data| hvar::Table::parse(data)),
                 b"MVAR" => face.mvar = data.get(range).and_then(|data| mvar::Table::parse(data)),
                 b"OS/2" => face.os_2 = data.get(range).and_then(|data| os2::Table::parse(data)),
+                b"STAT" => face.stat = data.get(range).and_then(stat::Table::parse),
                 b"SVG " => face.svg_ = data.get(range),
                 b"VORG" => face.vorg = data.get(range).and_then(|data| vorg::Table::parse(data)),
                 b"VVAR" => face.vvar = data.get(range).and_then(|data| hvar::Table::parse(data)),
@@ -716,7 +884,7 @@ impl<'a> Face<'a> {
                 b"cmap" => face.cmap = data.get(range).and_then(|data| cmap::parse(data)),
                 b"fvar" => face.fvar = data.get(range).and_then(|data| fvar::Table::parse(data)),
                 b"glyf" => face.glyf = data.get(range),
-                b"gvar" => face.gvar = data.get(range).and_then(|data| gvar::Table::parse(data)),
+                b"gvar" => gvar = data.get(range),
                 b"head" => face.head = data.get(range).and_then(|data| head::parse(data)).unwrap_or_default(),
                 b"hhea" => face.hhea = data.get(range).and_then(|data| hhea::parse(data)).unwrap_or_default(),
                 b"hmtx" => hmtx = data.get(range),
@@ -749,6 +917,10 @@ impl<'a> Face<'a> {
             face.coordinates.len = fvar.axes().count().min(MAX_VAR_COORDS as usize) as u8;
         }
 
+        if let (Some(fvar), Some(data)) = (face.fvar, gvar) {
+            face.gvar = gvar::Table::parse(data, fvar.axis_count(), face.number_of_glyphs);
+        }
+
         if let Some(data) = hmtx {
             if let Some(number_of_h_metrics) = hhea::number_of_h_metrics(face.hhea) {
                 face.hmtx = hmtx::Table::parse(data, number_of_h_metrics, face.number_of_glyphs);
@@ -821,34 +993,59 @@ impl<'a> Face<'a> {
     ///
     /// An iterator can be empty.
     ///
+    /// Iteration doesn't allocate. Records store only the raw name bytes,
+    /// which can be decoded on demand using `Name::to_string`.
+    /// Decoding requires the `std` or `alloc` feature.
+    ///
     /// [Name Records]: https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-records
     #[inline]
     pub fn names(&self) -> Names {
         self.name.unwrap_or_default()
     }
 
+    /// Returns an iterator over [Name Records] with public fields.
+    ///
+    /// Same as `Face::names`, but yields `NameRecord`.
+    /// Iteration doesn't allocate and names can be decoded on demand using `NameRecord::to_string`.
+    ///
+    /// [Name Records]: https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-records
+    #[inline]
+    pub fn name_records(&self) -> NameRecords<'_> {
+        NameRecords::new(self.names())
+    }
+
     /// Checks that face is marked as *Regular*.
     ///
-    /// Returns `false` when OS/2 table is not present.
+    /// Uses `OS/2.fsSelection` when OS/2 table is present.
+    /// Otherwise, a face is *Regular* when `head.macStyle` is neither *Bold* nor *Italic*.
     #[inline]
     pub fn is_regular(&self) -> bool {
-        try_opt_or!(self.os_2, false).is_regular()
+        match self.os_2 {
+            Some(os_2) => os_2.is_regular(),
+            None => head::is_regular(self.head),
+        }
     }
 
     /// Checks that face is marked as *Italic*.
     ///
-    /// Returns `false` when OS/2 table is not present.
+    /// Uses `OS/2.fsSelection` when OS/2 table is present and `head.macStyle` otherwise.
     #[inline]
     pub fn is_italic(&self) -> bool {
-        try_opt_or!(self.os_2, false).is_italic()
+        match self.os_2 {
+            Some(os_2) => os_2.is_italic(),
+            None => head::is_italic(self.head),
+        }
     }
 
     /// Checks that face is marked as *Bold*.
     ///
-    /// Returns `false` when OS/2 table is not present.
+    /// Uses `OS/2.fsSelection` when OS/2 table is present and `head.macStyle` otherwise.
     #[inline]
     pub fn is_bold(&self) -> bool {
-        try_opt_or!(self.os_2, false).is_bold()
+        match self.os_2 {
+            Some(os_2) => os_2.is_bold(),
+            None => head::is_bold(self.head),
+        }
     }
 
     /// Checks that face is marked as *Oblique*.
@@ -1045,6 +1242,33 @@ impl<'a> Face<'a> {
         head::units_per_em(self.head)
     }
 
+    /// Checks that face's units per EM is a power of two, like 1024 or 2048.
+    ///
+    /// In this case, scaling from font units can use a shift instead of a division.
+    ///
+    /// Returns `false` when units per EM is not set or invalid.
+    #[inline]
+    pub fn units_per_em_is_power_of_two(&self) -> bool {
+        match self.units_per_em() {
+            Some(n) => n.is_power_of_two(),
+            None => false,
+        }
+    }
+
+    /// Returns face's creation date.
+    #[inline]
+    pub fn created(&self) -> LongDateTime {
+        // unwrap is safe, because this method cannot fail.
+        head::created(self.head).unwrap()
+    }
+
+    /// Returns face's modification date.
+    #[inline]
+    pub fn modified(&self) -> LongDateTime {
+        // unwrap is safe, because this method cannot fail.
+        head::modified(self.head).unwrap()
+    }
+
     /// Returns face's x height.
     ///
     /// This method is affected by variation axes.
@@ -1131,6 +1355,22 @@ impl<'a> Face<'a> {
         Some(metrics)
     }
 
+    /// Returns face's caret slope.
+    ///
+    /// Text editors should draw the caret along this slope for italic faces.
+    ///
+    /// This method is affected by variation axes.
+    #[inline]
+    pub fn caret_slope(&self) -> CaretSlope {
+        let mut slope = hhea::caret_slope(self.hhea);
+        if self.is_variable() {
+            self.apply_metrics_variation_to(Tag::from_bytes(b"hcrs"), &mut slope.rise);
+            self.apply_metrics_variation_to(Tag::from_bytes(b"hcrn"), &mut slope.run);
+            self.apply_metrics_variation_to(Tag::from_bytes(b"hcof"), &mut slope.offset);
+        }
+        slope
+    }
+
     /// Returns a total number of glyphs in the face.
     ///
     /// Never zero.
@@ -1152,26 +1392,64 @@ impl<'a> Face<'a> {
         self.cmap.unwrap_or_default()
     }
 
+    /// Returns the raw data of the selected
+    /// [character to glyph index mapping](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap)
+    /// subtable.
+    ///
+    /// The selected subtable is the first Unicode one,
+    /// which is also the first one checked by `Face::glyph_index`.
+    /// Unicode Variation Sequences (14) subtables are skipped,
+    /// since they are not character maps.
+    ///
+    /// Useful if you want to parse a subtable format that isn't supported by us.
+    /// Use `Face::character_mapping_subtables` to get the subtable format.
+    ///
+    /// Returns `None` when `cmap` table is not present or has no Unicode subtables.
+    #[inline]
+    pub fn cmap_subtable_data(&self) -> Option<&'a [u8]> {
+        self.cmap?
+            .find(|subtable| {
+                subtable.is_unicode()
+                    && subtable.format() != cmap::Format::UnicodeVariationSequences
+            })
+            .map(|subtable| subtable.data())
+    }
+
     /// Resolves a Glyph ID for a code point.
     ///
     /// Returns `None` instead of `0` when glyph is not found.
     ///
+    /// Surrogates cannot be represented by `char`. Noncharacters, like U+FFFE and U+FFFF,
+    /// are looked up as any other code point, but the U+FFFF sentinel of the
+    /// Segment Mapping to Delta Values (4) subtable never maps to a real glyph.
+    ///
     /// All subtable formats except Mixed Coverage (8) are supported.
     ///
+    /// When the face has no Unicode subtables, a Macintosh Roman (1, 0) subtable is used
+    /// and the character is encoded as Mac Roman first.
+    ///
     /// If you need a more low-level control, prefer `Face::character_mapping_subtables`.
     #[inline]
     pub fn glyph_index(&self, c: char) -> Option<GlyphId> {
+        let mut has_unicode = false;
         for encoding in self.character_mapping_subtables() {
             if !encoding.is_unicode() {
                 continue;
             }
 
+            has_unicode = true;
             if let Some(id) = encoding.glyph_index(u32::from(c)) {
                 return Some(id);
             }
         }
 
-        None
+        if has_unicode {
+            return None;
+        }
+
+        self.character_mapping_subtables()
+            .find(|encoding| encoding.is_mac_roman())
+            .and_then(|encoding| encoding.glyph_index_mac_roman(c))
     }
 
     /// Resolves a variation of a Glyph ID from two code points.
@@ -1195,34 +1473,89 @@ impl<'a> Face<'a> {
 
     /// Returns glyph's horizontal advance.
     ///
+    /// The advance is read from `hmtx` as is, so the `USE_MY_METRICS` flag of composite glyphs
+    /// is ignored. Use `glyph_hor_advance_use_my_metrics` to honor it.
+    ///
     /// This method is affected by variation axes.
     #[inline]
     pub fn glyph_hor_advance(&self, glyph_id: GlyphId) -> Option<u16> {
+        self.glyph_hor_advance_impl(glyph_id, None)
+    }
+
+    /// Same as `glyph_hor_advance`, but uses precomputed variation data.
+    #[inline]
+    pub fn glyph_hor_advance_cached(
+        &self,
+        glyph_id: GlyphId,
+        cache: &VariationInstanceCache,
+    ) -> Option<u16> {
+        self.glyph_hor_advance_impl(glyph_id, self.valid_cache(cache).map(|c| &c.hvar))
+    }
+
+    fn glyph_hor_advance_impl(
+        &self,
+        glyph_id: GlyphId,
+        scalars: Option<&RegionScalars>,
+    ) -> Option<u16> {
         let mut advance = self.hmtx?.advance(glyph_id)? as f32;
 
         if self.is_variable() {
             // Ignore variation offset when `hvar` is not set.
             if let Some(hvar_data) = self.hvar {
                 // We can't use `round()` in `no_std`, so this is the next best thing.
-                advance += hvar::glyph_advance_offset(hvar_data, glyph_id, self.coords())? + 0.5;
+                advance += hvar::glyph_advance_offset(hvar_data, glyph_id, self.coords(), scalars)? + 0.5;
             }
         }
 
         u16::try_num_from(advance)
     }
 
+    /// Returns glyph's horizontal advance, honoring the `USE_MY_METRICS` flag.
+    ///
+    /// When a component of a composite `glyf` glyph has the `USE_MY_METRICS` flag set,
+    /// the advance of this component is returned. Otherwise, this is the same as
+    /// `glyph_hor_advance`.
+    ///
+    /// Unlike `glyph_hor_advance`, this method has to parse `glyf` composite glyphs.
+    /// To use precomputed variation data as well, pass `use_my_metrics_glyph`
+    /// to `glyph_hor_advance_cached`.
+    ///
+    /// This method is affected by variation axes.
+    #[inline]
+    pub fn glyph_hor_advance_use_my_metrics(&self, glyph_id: GlyphId) -> Option<u16> {
+        self.glyph_hor_advance(self.use_my_metrics_glyph(glyph_id))
+    }
+
     /// Returns glyph's vertical advance.
     ///
     /// This method is affected by variation axes.
     #[inline]
     pub fn glyph_ver_advance(&self, glyph_id: GlyphId) -> Option<u16> {
+        self.glyph_ver_advance_impl(glyph_id, None)
+    }
+
+    /// Same as `glyph_ver_advance`, but uses precomputed variation data.
+    #[inline]
+    pub fn glyph_ver_advance_cached(
+        &self,
+        glyph_id: GlyphId,
+        cache: &VariationInstanceCache,
+    ) -> Option<u16> {
+        self.glyph_ver_advance_impl(glyph_id, self.valid_cache(cache).map(|c| &c.vvar))
+    }
+
+    fn glyph_ver_advance_impl(
+        &self,
+        glyph_id: GlyphId,
+        scalars: Option<&RegionScalars>,
+    ) -> Option<u16> {
         let mut advance = self.vmtx?.advance(glyph_id)? as f32;
 
         if self.is_variable() {
             // Ignore variation offset when `vvar` is not set.
             if let Some(vvar_data) = self.vvar {
                 // We can't use `round()` in `no_std`, so this is the next best thing.
-                advance += hvar::glyph_advance_offset(vvar_data, glyph_id, self.coords())? + 0.5;
+                advance += hvar::glyph_advance_offset(vvar_data, glyph_id, self.coords(), scalars)? + 0.5;
             }
         }
 
@@ -1231,34 +1564,102 @@ impl<'a> Face<'a> {
 
     /// Returns glyph's horizontal side bearing.
     ///
+    /// The side bearing is read from `hmtx` as is, so the `USE_MY_METRICS` flag
+    /// of composite glyphs is ignored. Use `glyph_hor_side_bearing_use_my_metrics` to honor it.
+    ///
     /// This method is affected by variation axes.
     #[inline]
     pub fn glyph_hor_side_bearing(&self, glyph_id: GlyphId) -> Option<i16> {
+        self.glyph_hor_side_bearing_impl(glyph_id, None)
+    }
+
+    /// Same as `glyph_hor_side_bearing`, but uses precomputed variation data.
+    #[inline]
+    pub fn glyph_hor_side_bearing_cached(
+        &self,
+        glyph_id: GlyphId,
+        cache: &VariationInstanceCache,
+    ) -> Option<i16> {
+        self.glyph_hor_side_bearing_impl(glyph_id, self.valid_cache(cache).map(|c| &c.hvar))
+    }
+
+    fn glyph_hor_side_bearing_impl(
+        &self,
+        glyph_id: GlyphId,
+        scalars: Option<&RegionScalars>,
+    ) -> Option<i16> {
         let mut bearing = self.hmtx?.side_bearing(glyph_id)? as f32;
 
         if self.is_variable() {
             // Ignore variation offset when `hvar` is not set.
             if let Some(hvar_data) = self.hvar {
                 // We can't use `round()` in `no_std`, so this is the next best thing.
-                bearing += hvar::glyph_side_bearing_offset(hvar_data, glyph_id, self.coords())? + 0.5;
+                bearing += hvar::glyph_side_bearing_offset(hvar_data, glyph_id, self.coords(), scalars)? + 0.5;
             }
         }
 
         i16::try_num_from(bearing)
     }
 
+    /// Returns glyph's horizontal side bearing, honoring the `USE_MY_METRICS` flag.
+    ///
+    /// When a component of a composite `glyf` glyph has the `USE_MY_METRICS` flag set,
+    /// the side bearing of this component is returned. Otherwise, this is the same as
+    /// `glyph_hor_side_bearing`.
+    ///
+    /// The offset of the component is not applied to the returned side bearing.
+    ///
+    /// This method is affected by variation axes.
+    #[inline]
+    pub fn glyph_hor_side_bearing_use_my_metrics(&self, glyph_id: GlyphId) -> Option<i16> {
+        self.glyph_hor_side_bearing(self.use_my_metrics_glyph(glyph_id))
+    }
+
+    /// Returns a glyph which horizontal metrics should be used for the specified glyph.
+    ///
+    /// This is a component with the `USE_MY_METRICS` flag set when the glyph is
+    /// a composite `glyf` glyph, and the glyph itself otherwise. Can be used to honor
+    /// the flag in any method that accepts a glyph ID, like `glyph_hor_advance_cached`.
+    #[inline]
+    pub fn use_my_metrics_glyph(&self, glyph_id: GlyphId) -> GlyphId {
+        match (self.loca, self.glyf) {
+            (Some(loca_table), Some(glyf_table)) => {
+                glyf::metrics_glyph(loca_table, glyf_table, glyph_id)
+            }
+            _ => glyph_id,
+        }
+    }
+
     /// Returns glyph's vertical side bearing.
     ///
     /// This method is affected by variation axes.
     #[inline]
     pub fn glyph_ver_side_bearing(&self, glyph_id: GlyphId) -> Option<i16> {
+        self.glyph_ver_side_bearing_impl(glyph_id, None)
+    }
+
+    /// Same as `glyph_ver_side_bearing`, but uses precomputed variation data.
+    #[inline]
+    pub fn glyph_ver_side_bearing_cached(
+        &self,
+        glyph_id: GlyphId,
+        cache: &VariationInstanceCache,
+    ) -> Option<i16> {
+        self.glyph_ver_side_bearing_impl(glyph_id, self.valid_cache(cache).map(|c| &c.vvar))
+    }
+
+    fn glyph_ver_side_bearing_impl(
+        &self,
+        glyph_id: GlyphId,
+        scalars: Option<&RegionScalars>,
+    ) -> Option<i16> {
         let mut bearing = self.vmtx?.side_bearing(glyph_id)? as f32;
 
         if self.is_variable() {
             // Ignore variation offset when `vvar` is not set.
             if let Some(vvar_data) = self.vvar {
                 // We can't use `round()` in `no_std`, so this is the next best thing.
-                bearing += hvar::glyph_side_bearing_offset(vvar_data, glyph_id, self.coords())? + 0.5;
+                bearing += hvar::glyph_side_bearing_offset(vvar_data, glyph_id, self.coords(), scalars)? + 0.5;
             }
         }
 
@@ -1271,6 +1672,28 @@ impl<'a> Face<'a> {
         self.vorg.map(|vorg| vorg.glyph_y_origin(glyph_id))
     }
 
+    /// Returns glyph's raw Type 2 charstring.
+    ///
+    /// Uses the `CFF` and `CFF2` tables as sources.
+    /// Subroutines are not resolved, so the charstring should be interpreted
+    /// using the font's global subroutines and, for CID-keyed fonts,
+    /// local subroutines of the Font DICT selected by the FDSelect.
+    ///
+    /// Returns `None` when the face has no `CFF` or `CFF2` table
+    /// or when the glyph ID is out of bounds.
+    #[inline]
+    pub fn glyph_charstring(&self, glyph_id: GlyphId) -> Option<&'a [u8]> {
+        if let Some(ref metadata) = self.cff1 {
+            return cff1::glyph_charstring(metadata, glyph_id);
+        }
+
+        if let Some(ref metadata) = self.cff2 {
+            return cff2::glyph_charstring(metadata, glyph_id);
+        }
+
+        None
+    }
+
     /// Returns glyph's name.
     ///
     /// Uses the `post` and `CFF` tables as sources.
@@ -1335,6 +1758,69 @@ impl<'a> Face<'a> {
             gdef.variation_delta(outer_index, inner_index, self.coordinates.as_slice()))
     }
 
+    /// Returns a [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub).
+    #[inline]
+    pub fn substitution_table(&self) -> Option<gsub::Gsub<'a>> {
+        self.gsub
+    }
+
+    /// Returns a [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos).
+    #[inline]
+    pub fn positioning_table(&self) -> Option<gpos::Gpos<'a>> {
+        self.gpos
+    }
+
+    /// Checks that a glyph is referenced by any GSUB or GPOS lookup.
+    ///
+    /// Checks coverage tables of all lookup subtables and glyphs produced by substitutions.
+    /// Glyphs that are referenced only via class definitions or as the second glyph
+    /// of a GPOS pair are not detected.
+    ///
+    /// This method is expensive, since all lookups of both tables have to be parsed
+    /// on each call. When checking all glyphs, like during subsetting,
+    /// the caller should collect the results once.
+    pub fn glyph_in_layout(&self, glyph_id: GlyphId) -> bool {
+        if let Some(ref table) = self.gsub {
+            for lookup in table.lookups() {
+                let lookup_type = lookup.lookup_type();
+                if lookup.subtables().any(|data| gsub::subtable_contains(lookup_type, data, glyph_id)) {
+                    return true;
+                }
+            }
+        }
+
+        if let Some(ref table) = self.gpos {
+            for lookup in table.lookups() {
+                let lookup_type = lookup.lookup_type();
+                if lookup.subtables().any(|data| gpos::subtable_contains(lookup_type, data, glyph_id)) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Returns an iterator over all ligatures of a feature.
+    ///
+    /// See `gsub::Gsub::ligatures` for details.
+    ///
+    /// Returns an empty iterator when `GSUB` table is not present.
+    #[inline]
+    pub fn ligatures(&self, feature: Tag) -> gsub::Ligatures<'a> {
+        self.gsub.map(|table| table.ligatures(feature)).unwrap_or_default()
+    }
+
+    /// Returns a ligature glyph for the exact sequence of components.
+    ///
+    /// See `gsub::Gsub::ligature_for` for details.
+    ///
+    /// Returns `None` when `GSUB` table is not present.
+    #[inline]
+    pub fn ligature_for(&self, components: &[GlyphId], feature: Tag) -> Option<GlyphId> {
+        self.gsub?.ligature_for(components, feature)
+    }
+
     /// Returns a iterator over kerning subtables.
     ///
     /// Supports both
@@ -1402,6 +1888,52 @@ impl<'a> Face<'a> {
         &self,
         glyph_id: GlyphId,
         builder: &mut dyn OutlineBuilder,
+    ) -> Option<Rect> {
+        self.outline_glyph_impl(glyph_id, builder, None)
+    }
+
+    /// Same as `outline_glyph`, but uses precomputed variation data.
+    ///
+    /// Only `CFF2` outlines benefit from it, since `gvar` doesn't use an Item Variation Store.
+    #[inline]
+    pub fn outline_glyph_cached(
+        &self,
+        glyph_id: GlyphId,
+        builder: &mut dyn OutlineBuilder,
+        cache: &VariationInstanceCache,
+    ) -> Option<Rect> {
+        self.outline_glyph_impl(glyph_id, builder, self.valid_cache(cache))
+    }
+
+    /// Same as `outline_glyph`, but also reports the orientation of each contour
+    /// via `OutlineBuilder::contour_orientation`.
+    #[inline]
+    pub fn outline_glyph_with_orientation(
+        &self,
+        glyph_id: GlyphId,
+        builder: &mut dyn OutlineBuilder,
+    ) -> Option<Rect> {
+        let mut builder = OrientationBuilder::new(builder);
+        self.outline_glyph(glyph_id, &mut builder)
+    }
+
+    /// Outlines the `.notdef` glyph.
+    ///
+    /// Same as `outline_glyph(GlyphId(0), builder)`. The `.notdef` glyph is usually a box
+    /// that can be rendered in place of missing characters.
+    ///
+    /// Returns `None` when the `.notdef` glyph is empty, which is allowed by the specification.
+    /// In this case, `builder` is not called.
+    #[inline]
+    pub fn notdef_outline(&self, builder: &mut dyn OutlineBuilder) -> Option<Rect> {
+        self.outline_glyph(GlyphId(0), builder)
+    }
+
+    fn outline_glyph_impl(
+        &self,
+        glyph_id: GlyphId,
+        builder: &mut dyn OutlineBuilder,
+        cache: Option<&VariationInstanceCache>,
     ) -> Option<Rect> {
         if let Some(ref gvar_table) = self.gvar {
             return gvar::outline(self.loca?, self.glyf?, gvar_table, self.coords(), glyph_id, builder);
@@ -1416,7 +1948,8 @@ impl<'a> Face<'a> {
         }
 
         if let Some(ref metadata) = self.cff2 {
-            return cff2::outline(metadata, self.coords(), glyph_id, builder);
+            let scalars = cache.map(|c| &c.cff2);
+            return cff2::outline(metadata, self.coords(), scalars, glyph_id, builder);
         }
 
         None
@@ -1428,6 +1961,10 @@ impl<'a> Face<'a> {
     /// since only the `glyf` table stores a bounding box. In case of CFF and variable fonts
     /// we have to actually outline a glyph to find it's bounding box.
     ///
+    /// For a non-variable `glyf` face this is a cheap glyph header read,
+    /// while for CFF the whole charstring has to be interpreted, which is significantly slower.
+    /// Cache the result if you need it often.
+    ///
     /// When a glyph is defined by a raster or a vector image,
     /// that can be obtained via `glyph_image()`,
     /// the bounding box must be calculated manually and this method will return `None`.
@@ -1444,6 +1981,76 @@ impl<'a> Face<'a> {
         self.outline_glyph(glyph_id, &mut DummyOutline)
     }
 
+    /// Returns the last point index of each contour of a simple `glyf` glyph.
+    ///
+    /// A contour starts right after the previous one ends, so the first contour
+    /// starts at point 0. Point indices match the ones used by `gvar`.
+    ///
+    /// Returns `None` for composite and empty glyphs,
+    /// and when the face has no `glyf` table.
+    #[inline]
+    pub fn glyph_contour_endpoints(&self, glyph_id: GlyphId) -> Option<LazyArray16<'a, u16>> {
+        glyf::glyph_contour_endpoints(self.loca?, self.glyf?, glyph_id)
+    }
+
+    /// Returns the number of contours of a `glyf` glyph with all components expanded.
+    ///
+    /// Unlike the glyph header, which stores `-1` for composite glyphs,
+    /// this method sums contours of all components, including nested ones.
+    /// Can be used to preallocate buffers before outlining.
+    ///
+    /// Returns `Some(0)` for empty glyphs.
+    ///
+    /// Returns `None` when the face has no `glyf` table, when glyph ID is out of range,
+    /// when a component is malformed or when components are nested too deeply.
+    #[inline]
+    pub fn glyph_total_contours(&self, glyph_id: GlyphId) -> Option<u16> {
+        if glyph_id.0 >= self.number_of_glyphs() {
+            return None;
+        }
+
+        glyf::glyph_total_contours(self.loca?, self.glyf?, glyph_id)
+    }
+
+    /// Checks that a glyph has the `OVERLAP_SIMPLE` or `OVERLAP_COMPOUND` flag set.
+    ///
+    /// Such glyphs can have overlapping contours or components, so a rasterizer
+    /// should use the non-zero fill rule or union the contours
+    /// to avoid dropouts in the overlapping regions.
+    ///
+    /// Only the `glyf` table stores these flags, so this method returns `false`
+    /// for all other outline formats and empty glyphs.
+    #[inline]
+    pub fn glyph_has_overlap_flag(&self, glyph_id: GlyphId) -> bool {
+        match (self.loca, self.glyf) {
+            (Some(loca_table), Some(glyf_table)) => {
+                glyf::glyph_has_overlap_flag(loca_table, glyf_table, glyph_id).unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns an estimated glyph's horizontal advance.
+    ///
+    /// The advance is calculated from the glyph's bounding box as `x_max + x_min`,
+    /// i.e. the right side bearing is assumed to be equal to the left one.
+    ///
+    /// This is a last-resort fallback for faces with a missing or malformed `hmtx` table
+    /// and should not be used instead of `Face::glyph_hor_advance`.
+    ///
+    /// Since it relies on `Face::glyph_bounding_box`, `glyf`, `CFF` and `CFF2` tables
+    /// are supported.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when glyph has no outline.
+    #[inline]
+    pub fn glyph_bbox_advance(&self, glyph_id: GlyphId) -> Option<u16> {
+        let bbox = self.glyph_bounding_box(glyph_id)?;
+        let advance = i32::from(bbox.x_max) + i32::from(bbox.x_min);
+        u16::try_from(advance).ok()
+    }
+
     /// Returns a bounding box that large enough to enclose any glyph from the face.
     #[inline]
     pub fn global_bounding_box(&self) -> Rect {
@@ -1486,6 +2093,22 @@ impl<'a> Face<'a> {
         None
     }
 
+    /// Returns how an existing bitmap strike should be scaled to the specified size.
+    ///
+    /// Parsed from the
+    /// [Embedded Bitmap Scaling Table](https://docs.microsoft.com/en-us/typography/opentype/spec/ebsc),
+    /// which is used for sizes that don't have their own `EBLC`/`EBDT` strike.
+    /// A caller should load a bitmap at `source_ppem_x`/`source_ppem_y`
+    /// and scale it by `scale_x`/`scale_y`.
+    ///
+    /// Returns `None` when `pixels_per_em` has no scaling entry.
+    ///
+    /// Note that `EBLC`/`EBDT` bitmaps are not decoded by this library yet.
+    #[inline]
+    pub fn bitmap_scale(&self, pixels_per_em: u16) -> Option<BitmapScale> {
+        self.ebsc?.scale(pixels_per_em)
+    }
+
     /// Returns a reference to a glyph's SVG image.
     ///
     /// A font can define a glyph using a raster or a vector image instead of a simple outline.
@@ -1502,12 +2125,97 @@ impl<'a> Face<'a> {
         self.svg_.and_then(|svg_data| svg::parse(svg_data, glyph_id))
     }
 
+    /// Returns a color glyph clip box.
+    ///
+    /// Renderers should use it to size the canvas of a
+    /// [`COLR`](https://docs.microsoft.com/en-us/typography/opentype/spec/colr) v1 glyph.
+    /// The clip box is in font units.
+    ///
+    /// Variable clip boxes are returned for the default instance.
+    ///
+    /// Returns `None` when `COLR` table is not present or has no clip box for this glyph.
+    #[inline]
+    pub fn color_glyph_clip_box(&self, glyph_id: GlyphId) -> Option<Rect> {
+        self.colr?.clip_box(glyph_id)
+    }
+
+    /// Returns the number of color palettes.
+    ///
+    /// Returns `0` when `CPAL` table is not present.
+    #[inline]
+    pub fn color_palettes_count(&self) -> u16 {
+        self.cpal.map(|cpal| cpal.palettes_count()).unwrap_or(0)
+    }
+
+    /// Returns all colors of a [`CPAL`](https://docs.microsoft.com/en-us/typography/opentype/spec/cpal)
+    /// palette.
+    ///
+    /// All palettes have the same number of colors, so `COLR` color indices
+    /// can be resolved against any of them.
+    ///
+    /// Returns `None` when `CPAL` table is not present, when `index` is out of range
+    /// or when the palette is malformed.
+    #[inline]
+    pub fn color_palette(&self, index: u16) -> Option<LazyArray16<'a, RgbaColor>> {
+        self.cpal?.palette(index)
+    }
+
     /// Returns an iterator over variation axes.
+    ///
+    /// Axes are yielded in the `fvar` order, which is the recommended order
+    /// for presenting axes to the user.
     #[inline]
     pub fn variation_axes(&self) -> VariationAxes {
         self.fvar.map(|fvar| fvar.axes()).unwrap_or_default()
     }
 
+    /// Returns an iterator over variation axes ordered by the `STAT` table.
+    ///
+    /// Same as `variation_axes`, but axes are reordered using the `STAT` axis ordering.
+    /// Falls back to the `fvar` order when `STAT` table is not present.
+    #[inline]
+    pub fn variation_axes_ordered_by_stat(&self) -> StatOrderedVariationAxes<'a> {
+        let axes = self.fvar.map(|fvar| fvar.axes()).unwrap_or_default();
+        StatOrderedVariationAxes::new(axes, self.stat)
+    }
+
+    /// Returns an iterator over named instances.
+    ///
+    /// Named instances are predefined coordinates of a variable font,
+    /// like Bold or Condensed.
+    #[inline]
+    pub fn named_instances(&self) -> NamedInstances<'a> {
+        self.fvar.map(|fvar| fvar.named_instances()).unwrap_or_default()
+    }
+
+    /// Returns the number of named instances.
+    ///
+    /// Returns `0` when font is not variable.
+    #[inline]
+    pub fn named_instances_count(&self) -> u16 {
+        self.fvar.map(|fvar| fvar.named_instances_count()).unwrap_or(0)
+    }
+
+    /// Returns a named instance by index.
+    ///
+    /// Returns `None` when `index` is out of bounds.
+    #[inline]
+    pub fn named_instance(&self, index: u16) -> Option<NamedInstance<'a>> {
+        self.fvar?.named_instance(index)
+    }
+
+    /// Returns an iterator over style attributes axis values.
+    ///
+    /// Each value has its name resolved from the `name` table,
+    /// which allows showing rows like "Weight: Bold".
+    ///
+    /// Returns an empty iterator when `STAT` table is not present.
+    #[inline]
+    pub fn style_axis_values(&self) -> AxisValues<'a> {
+        let names = self.name.unwrap_or_default();
+        self.stat.map(|stat| stat.axis_values(names)).unwrap_or_default()
+    }
+
     /// Sets a variation axis coordinate.
     ///
     /// This is the only mutable method in the library.
@@ -1542,6 +2250,15 @@ impl<'a> Face<'a> {
         Some(())
     }
 
+    /// Returns an iterator over `avar` segment maps.
+    ///
+    /// Yields a map per axis, each as normalized `fromCoordinate` and `toCoordinate` pairs.
+    /// Returns an empty iterator when `avar` table is not present.
+    #[inline]
+    pub fn avar_segment_maps(&self) -> SegmentMaps<'a> {
+        self.avar.map(|avar| avar.segment_maps()).unwrap_or_default()
+    }
+
     /// Returns the current normalized variation coordinates.
     #[inline]
     pub fn variation_coordinates(&self) -> &[NormalizedCoordinate] {
@@ -1554,6 +2271,32 @@ impl<'a> Face<'a> {
         self.coordinates.as_slice().iter().any(|c| c.0 != 0)
     }
 
+    /// Precomputes variation data for the current variation coordinates.
+    ///
+    /// See `VariationInstanceCache` for details.
+    pub fn variation_instance_cache(&self) -> VariationInstanceCache {
+        let coords = self.coords();
+        VariationInstanceCache {
+            coordinates: self.coordinates.clone(),
+            hvar: self.hvar.map(|t| t.region_scalars(coords)).unwrap_or_default(),
+            vvar: self.vvar.map(|t| t.region_scalars(coords)).unwrap_or_default(),
+            cff2: self.cff2.as_ref().map(|m| cff2::region_scalars(m, coords)).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the cache only when it was created for the current variation coordinates.
+    #[inline]
+    fn valid_cache<'c>(
+        &self,
+        cache: &'c VariationInstanceCache,
+    ) -> Option<&'c VariationInstanceCache> {
+        if cache.coordinates.as_slice() == self.coords() {
+            Some(cache)
+        } else {
+            None
+        }
+    }
+
     #[inline]
     fn metrics_var_offset(&self, tag: Tag) -> f32 {
         self.mvar.and_then(|table| table.metrics_offset(tag, self.coords())).unwrap_or(0.0)
@@ -1606,6 +2349,35 @@ pub fn fonts_in_collection(data: &[u8]) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::writer::Builder;
+
+    /// Returns an offset of a table record with the specified tag in the table directory.
+    fn table_record_offset(data: &[u8], tag: &[u8; 4]) -> usize {
+        let count = usize::from(u16::from_be_bytes([data[4], data[5]]));
+        (0..count).map(|i| 12 + i * 16).find(|i| &data[*i..*i + 4] == tag).unwrap()
+    }
+
+    /// Appends `table` to the font data and points the table record with the `old` tag to it,
+    /// renaming the record to `new`.
+    ///
+    /// Table records must stay sorted, so `new` must sort at the same position as `old`.
+    fn replace_table(data: &mut std::vec::Vec<u8>, old: &[u8; 4], new: &[u8; 4], table: &[u8]) {
+        let offset = data.len() as u32;
+        let length = table.len() as u32;
+        data.extend_from_slice(table);
+
+        let record = table_record_offset(data, old);
+        data[record..record + 4].copy_from_slice(new);
+        data[record + 8..record + 12].copy_from_slice(&offset.to_be_bytes());
+        data[record + 12..record + 16].copy_from_slice(&length.to_be_bytes());
+    }
+
+    /// Returns an offset of a table with the specified tag using the table directory.
+    fn table_offset(data: &[u8], tag: &[u8; 4]) -> usize {
+        let record = table_record_offset(data, tag);
+        u32::from_be_bytes([data[record + 8], data[record + 9],
+                            data[record + 10], data[record + 11]]) as usize
+    }
 
     #[test]
     fn empty_font() {
@@ -1683,4 +2455,822 @@ mod tests {
         assert_eq!(Face::from_slice(data, std::u32::MAX).unwrap_err(),
                    FaceParsingError::FaceIndexOutOfBounds);
     }
+
+    #[test]
+    fn cmap_subtable_data() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let subtable_data = face.cmap_subtable_data().unwrap();
+        // Segment mapping to delta values.
+        assert_eq!(&subtable_data[0..2], &[0x00, 0x04]);
+        // Bounded by the subtable length.
+        assert_eq!(subtable_data.len(), usize::from(u16::from_be_bytes([subtable_data[2], subtable_data[3]])));
+    }
+
+    #[test]
+    fn cmap_subtable_data_skips_variation_sequences() {
+        let mut data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let cmap = table_offset(&data, b"cmap");
+        // Point the first encoding record to the format 12 subtable, like the second one,
+        // and rewrite it into an empty format 14 one.
+        let offset = [data[cmap + 16], data[cmap + 17], data[cmap + 18], data[cmap + 19]];
+        data[cmap + 8..cmap + 12].copy_from_slice(&offset);
+        let subtable = cmap + u32::from_be_bytes(offset) as usize;
+        data[subtable..subtable + 10].copy_from_slice(&[
+            0x00, 0x0E, // format: 14
+            0x00, 0x00, 0x00, 0x0A, // length: 10
+            0x00, 0x00, 0x00, 0x00, // number of variation selector records: 0
+        ]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        let first = face.character_mapping_subtables().next().unwrap();
+        assert_eq!(first.format(), cmap::Format::UnicodeVariationSequences);
+        // Falls back to the Windows format 4 subtable.
+        assert_eq!(&face.cmap_subtable_data().unwrap()[0..2], &[0x00, 0x04]);
+    }
+
+    #[test]
+    fn glyph_bbox_advance() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        // A symmetric glyph.
+        assert_eq!(face.glyph_bbox_advance(GlyphId(2)), face.glyph_hor_advance(GlyphId(2)));
+        assert_eq!(face.glyph_bbox_advance(GlyphId(2)), Some(544));
+        // An asymmetric glyph. The estimate is close, but not exact.
+        assert_eq!(face.glyph_hor_advance(GlyphId(13)), Some(486));
+        assert_eq!(face.glyph_bbox_advance(GlyphId(13)), Some(550));
+        // Space has no outline.
+        assert_eq!(face.glyph_bbox_advance(GlyphId(1)), None);
+    }
+
+    #[test]
+    fn cff_glyph_bounding_box() {
+        let data = std::fs::read("benches/fonts/SourceSansPro-Regular.otf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert!(face.glyf.is_none());
+
+        // The bounding box is calculated by outlining a glyph.
+        // Matches the `glyf` bounding boxes of SourceSansPro-Regular.ttf.
+        let a = face.glyph_index('A').unwrap();
+        assert_eq!(face.glyph_bounding_box(a), Some(Rect { x_min: 3, y_min: 0, x_max: 541, y_max: 656 }));
+        let g = face.glyph_index('g').unwrap();
+        assert_eq!(face.glyph_bounding_box(g), Some(Rect { x_min: 45, y_min: -224, x_max: 492, y_max: 498 }));
+        // Space has no outline.
+        let space = face.glyph_index(' ').unwrap();
+        assert_eq!(face.glyph_bounding_box(space), None);
+    }
+
+    #[test]
+    fn bitmap_scale_without_ebsc() {
+        for path in &["fonts/SourceSansPro-Regular-Tiny.ttf",
+                      "benches/fonts/SourceSansPro-Regular.otf"] {
+            let data = std::fs::read(path).unwrap();
+            let face = Face::from_slice(&data, 0).unwrap();
+            assert!(face.table_data(Tag::from_bytes(b"EBSC")).is_none());
+            assert_eq!(face.bitmap_scale(12), None);
+            assert_eq!(face.bitmap_scale(24), None);
+        }
+    }
+
+    #[test]
+    fn bitmap_scale() {
+        let mut data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let mut ebsc = vec![
+            0x00, 0x02, 0x00, 0x00, // version: 2.0
+            0x00, 0x00, 0x00, 0x01, // number of sizes: 1
+        ];
+        ebsc.extend_from_slice(&[0; 24]); // hori and vert line metrics
+        ebsc.extend_from_slice(&[24, 24, 12, 12]); // 24 ppem from 12 ppem
+
+        // Replace the GDEF table record, which keeps the table records sorted.
+        replace_table(&mut data, b"GDEF", b"EBSC", &ebsc);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.bitmap_scale(24), Some(BitmapScale {
+            source_ppem_x: 12,
+            source_ppem_y: 12,
+            scale_x: 2.0,
+            scale_y: 2.0,
+        }));
+        assert_eq!(face.bitmap_scale(12), None);
+    }
+
+    #[test]
+    fn glyph_id_from_u16() {
+        let g: GlyphId = 42u16.into();
+        assert_eq!(g, GlyphId(42));
+        assert_eq!(g.get(), 42);
+        assert_eq!(g.0, 42);
+    }
+
+    #[test]
+    fn contour_orientation() {
+        struct Builder(std::vec::Vec<bool>);
+        impl OutlineBuilder for Builder {
+            fn move_to(&mut self, _: f32, _: f32) {}
+            fn line_to(&mut self, _: f32, _: f32) {}
+            fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) {}
+            fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) {}
+            fn close(&mut self) {}
+            fn contour_orientation(&mut self, clockwise: bool) {
+                self.0.push(clockwise);
+            }
+        }
+
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+
+        // 'O' has an outer contour and a hole.
+        let glyph_id = face.glyph_index('O').unwrap();
+        let mut builder = Builder(std::vec::Vec::new());
+        face.outline_glyph_with_orientation(glyph_id, &mut builder).unwrap();
+        assert_eq!(builder.0, &[true, false]);
+
+        // 'L' has a single contour.
+        let mut builder = Builder(std::vec::Vec::new());
+        face.outline_glyph_with_orientation(GlyphId(13), &mut builder).unwrap();
+        assert_eq!(builder.0, &[true]);
+
+        // Not reported by a regular outlining.
+        let mut builder = Builder(std::vec::Vec::new());
+        face.outline_glyph(glyph_id, &mut builder).unwrap();
+        assert!(builder.0.is_empty());
+    }
+
+    #[test]
+    fn gsub_gpos_tables() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+
+        let gsub_data = face.table_data(Tag::from_bytes(b"GSUB")).unwrap();
+        let gsub = gsub::Gsub::parse(gsub_data).unwrap();
+        assert_eq!(gsub.scripts().count(), 4);
+        assert_eq!(gsub.lookups().count(), 7);
+        assert_eq!(face.substitution_table().unwrap().lookups().count(), 7);
+
+        let gpos = face.positioning_table().unwrap();
+        assert_eq!(gpos.features().next().unwrap().tag(), Tag::from_bytes(b"kern"));
+        assert_eq!(gpos.lookups().count(), 1);
+    }
+
+    #[test]
+    fn script_by_tag() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let gsub = face.substitution_table().unwrap();
+        let scripts = gsub.scripts();
+
+        let (index, script) = scripts.script_by_tag(Tag::from_bytes(b"grek")).unwrap();
+        assert_eq!(index, ScriptIndex(2));
+        assert_eq!(script.tag(), Tag::from_bytes(b"grek"));
+        assert_eq!(scripts.script_by_tag(Tag::from_bytes(b"DFLT")).unwrap().0, ScriptIndex(0));
+        assert_eq!(scripts.script_by_tag(Tag::from_bytes(b"latn")).unwrap().0, ScriptIndex(3));
+        assert!(scripts.script_by_tag(Tag::from_bytes(b"arab")).is_none());
+    }
+
+    #[test]
+    fn indices_by_tag() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let gsub = face.substitution_table().unwrap();
+
+        assert_eq!(gsub.script_index_by_tag(Tag::from_bytes(b"latn")), Some(ScriptIndex(3)));
+        assert_eq!(gsub.script_index_by_tag(Tag::from_bytes(b"arab")), None);
+
+        let liga = Tag::from_bytes(b"liga");
+        let indices: std::vec::Vec<_> = gsub.feature_indices_by_tag(liga).collect();
+        assert_eq!(indices, (18..27).map(FeatureIndex).collect::<std::vec::Vec<_>>());
+        for index in indices {
+            assert_eq!(gsub.feature_at(index).unwrap().tag(), liga);
+        }
+
+        assert_eq!(gsub.feature_indices_by_tag(Tag::from_bytes(b"smcp")).count(), 0);
+    }
+
+    #[test]
+    fn gsub_iterators_reset() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let gsub = face.substitution_table().unwrap();
+
+        let mut scripts = gsub.scripts();
+        let tags: std::vec::Vec<_> = scripts.by_ref().map(|s| s.tag()).collect();
+        assert!(scripts.next().is_none());
+        scripts.reset();
+        assert_eq!(scripts.map(|s| s.tag()).collect::<std::vec::Vec<_>>(), tags);
+
+        let mut features = gsub.features();
+        let tags: std::vec::Vec<_> = features.by_ref().map(|f| f.tag()).collect();
+        assert!(features.next().is_none());
+        features.reset();
+        assert_eq!(features.map(|f| f.tag()).collect::<std::vec::Vec<_>>(), tags);
+
+        let mut lookups = gsub.lookups();
+        let count = lookups.by_ref().count();
+        assert!(lookups.next().is_none());
+        lookups.reset();
+        assert_eq!(lookups.count(), count);
+
+        let mut variations = gsub.feature_variations();
+        assert!(variations.next().is_none());
+        variations.reset();
+        assert!(variations.next().is_none());
+    }
+
+    #[test]
+    fn gsub_size_hints() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let gsub = face.substitution_table().unwrap();
+
+        let mut scripts = gsub.scripts();
+        assert_eq!(scripts.size_hint(), (0, Some(4)));
+        let script = scripts.next().unwrap();
+        assert_eq!(scripts.size_hint(), (0, Some(3)));
+        assert_eq!(script.languages().size_hint().1, Some(script.languages().count()));
+
+        let mut features = gsub.features();
+        assert_eq!(features.size_hint(), (0, Some(41)));
+        features.next();
+        assert_eq!(features.size_hint(), (0, Some(40)));
+
+        let lookups = gsub.lookups();
+        assert_eq!(lookups.size_hint(), (0, Some(lookups.count())));
+        assert_eq!(gsub.feature_variations().size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn gsub_list_lengths() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let gsub = face.substitution_table().unwrap();
+        assert_eq!(gsub.scripts().len(), 4);
+        assert_eq!(gsub.features().len(), 41);
+        assert_eq!(usize::from(gsub.lookups().len()), gsub.lookups().count());
+        assert!(gsub.feature_variations().is_empty());
+    }
+
+    #[test]
+    fn language_tags() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let gsub = face.substitution_table().unwrap();
+
+        for script in gsub.scripts() {
+            let tags: std::vec::Vec<_> = script.language_tags().collect();
+            assert_eq!(tags.len(), script.languages().count());
+            for (index, tag) in tags {
+                assert_eq!(script.language_at(index).unwrap().tag(), tag);
+            }
+        }
+
+        let (_, latn) = gsub.scripts().script_by_tag(Tag::from_bytes(b"latn")).unwrap();
+        assert!(latn.language_tags().next().is_some());
+    }
+
+    #[test]
+    fn feature_by_tag() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let gsub = face.substitution_table().unwrap();
+        let features = gsub.features();
+
+        // Each feature is repeated for every language system.
+        let (index, feature) = features.feature_by_tag(Tag::from_bytes(b"liga")).unwrap();
+        assert_eq!(index, FeatureIndex(18));
+        assert_eq!(feature.tag(), Tag::from_bytes(b"liga"));
+        assert_eq!(features.feature_by_tag(Tag::from_bytes(b"dnom")).unwrap().0, FeatureIndex(0));
+        assert!(features.feature_by_tag(Tag::from_bytes(b"smcp")).is_none());
+    }
+
+    #[test]
+    fn lookups_of_type() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let gsub = face.substitution_table().unwrap();
+        // Ligature substitution.
+        let ligatures: std::vec::Vec<_> = gsub.lookups_of_type(4).map(|(i, _)| i).collect();
+        assert_eq!(ligatures, &[LookupIndex(5)]);
+        assert_eq!(gsub.lookups_of_type(1).count(), 5);
+        assert_eq!(gsub.lookups_of_type(6).count(), 1);
+    }
+
+    #[test]
+    fn ligature_for() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let liga = Tag::from_bytes(b"liga");
+        let (f, t) = (GlyphId(33), GlyphId(47));
+        assert_eq!(face.ligature_for(&[f, f, t], liga), Some(GlyphId(85)));
+        assert_eq!(face.ligature_for(&[f, f], liga), Some(GlyphId(83)));
+        assert_eq!(face.ligature_for(&[f, t], liga), Some(GlyphId(84)));
+        assert_eq!(face.ligature_for(&[f, t, t], liga), None);
+        assert_eq!(face.ligature_for(&[f], liga), None);
+    }
+
+    #[test]
+    fn ligatures() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+
+        let f = face.glyph_index('f').unwrap();
+        let t = face.glyph_index('t').unwrap();
+        let ligatures: std::vec::Vec<_> = face.ligatures(Tag::from_bytes(b"liga"))
+            .map(|l| {
+                let mut components = vec![l.first_component];
+                components.extend(l.components);
+                (components, l.glyph)
+            })
+            .collect();
+
+        assert_eq!(ligatures, vec![
+            (vec![f, f, t], GlyphId(85)),
+            (vec![f, f], GlyphId(83)),
+            (vec![f, t], GlyphId(84)),
+        ]);
+
+        assert_eq!(face.ligatures(Tag::from_bytes(b"frac")).count(), 0);
+    }
+
+    #[test]
+    fn glyph_hor_advance_use_my_metrics() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let glyf_table = face.glyf.unwrap();
+        let loca_table = face.loca.unwrap();
+
+        // 'i' is a dotless 'i' with USE_MY_METRICS and a dot.
+        assert_eq!(glyf::metrics_glyph(loca_table, glyf_table, GlyphId(36)), GlyphId(81));
+        assert_eq!(face.glyph_hor_advance_use_my_metrics(GlyphId(36)),
+                   face.glyph_hor_advance(GlyphId(81)));
+        assert_eq!(face.glyph_hor_side_bearing_use_my_metrics(GlyphId(36)),
+                   face.glyph_hor_side_bearing(GlyphId(81)));
+        // A composite glyph that references another composite glyph.
+        assert_eq!(glyf::metrics_glyph(loca_table, glyf_table, GlyphId(98)), GlyphId(81));
+        assert_eq!(face.glyph_hor_advance_use_my_metrics(GlyphId(98)),
+                   face.glyph_hor_advance(GlyphId(81)));
+        // A simple glyph.
+        assert_eq!(glyf::metrics_glyph(loca_table, glyf_table, GlyphId(13)), GlyphId(13));
+        assert_eq!(face.glyph_hor_advance_use_my_metrics(GlyphId(13)),
+                   face.glyph_hor_advance(GlyphId(13)));
+        // The plain method still reads `hmtx` directly.
+        assert_eq!(face.glyph_hor_advance(GlyphId(36)), face.hmtx.unwrap().advance(GlyphId(36)));
+
+        assert_eq!(face.use_my_metrics_glyph(GlyphId(36)), GlyphId(81));
+        assert_eq!(face.use_my_metrics_glyph(GlyphId(13)), GlyphId(13));
+    }
+
+    #[test]
+    fn decode_family_name_only() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let family = face.names()
+            .filter(|name| name.name_id() == name_id::FAMILY && name.is_unicode())
+            .find_map(|name| name.to_string());
+        assert_eq!(family, Some("Source Sans Pro".into()));
+        // Other records are available as raw bytes.
+        assert_eq!(face.names().count(), 7);
+        assert!(face.names().all(|name| !name.name().is_empty()));
+
+        let family = face.name_records()
+            .find(|record| record.name_id == name_id::FAMILY && record.is_unicode())
+            .unwrap();
+        assert_eq!(family.platform_id, PlatformId::Windows);
+        assert_eq!(family.to_string(), Some("Source Sans Pro".into()));
+        assert_eq!(face.name_records().count(), 7);
+        assert!(face.names().map(|name| name.record()).eq(face.name_records()));
+    }
+
+    #[test]
+    fn vertical_metrics() {
+        let mut data = vec![
+            0x00, 0x01, 0x00, 0x00, // magic: TrueType
+            0x00, 0x05, // number of tables: 5
+            0x00, 0x00, // search range: 0
+            0x00, 0x00, // entry selector: 0
+            0x00, 0x00, // range shift: 0
+            // Table Record [0]
+            0x68, 0x65, 0x61, 0x64, // tag: head
+            0x00, 0x00, 0x00, 0x00, // checksum: 0
+            0x00, 0x00, 0x00, 0x92, // offset: 146
+            0x00, 0x00, 0x00, 0x36, // length: 54
+            // Table Record [1]
+            0x68, 0x68, 0x65, 0x61, // tag: hhea
+            0x00, 0x00, 0x00, 0x00, // checksum: 0
+            0x00, 0x00, 0x00, 0x92, // offset: 146
+            0x00, 0x00, 0x00, 0x24, // length: 36
+            // Table Record [2]
+            0x6D, 0x61, 0x78, 0x70, // tag: maxp
+            0x00, 0x00, 0x00, 0x00, // checksum: 0
+            0x00, 0x00, 0x00, 0x5C, // offset: 92
+            0x00, 0x00, 0x00, 0x06, // length: 6
+            // Table Record [3]
+            0x76, 0x68, 0x65, 0x61, // tag: vhea
+            0x00, 0x00, 0x00, 0x00, // checksum: 0
+            0x00, 0x00, 0x00, 0x6E, // offset: 110
+            0x00, 0x00, 0x00, 0x24, // length: 36
+            // Table Record [4]
+            0x76, 0x6D, 0x74, 0x78, // tag: vmtx
+            0x00, 0x00, 0x00, 0x00, // checksum: 0
+            0x00, 0x00, 0x00, 0x62, // offset: 98
+            0x00, 0x00, 0x00, 0x0C, // length: 12
+            // maxp
+            0x00, 0x00, 0x50, 0x00, // version: 0.3125
+            0x00, 0x04, // number of glyphs: 4
+            // vmtx
+            0x00, 0x64, // advance height [0]: 100
+            0x00, 0x0A, // top side bearing [0]: 10
+            0x00, 0xC8, // advance height [1]: 200
+            0x00, 0x14, // top side bearing [1]: 20
+            0x00, 0x1E, // top side bearing [2]: 30
+            0x00, 0x28, // top side bearing [3]: 40
+            // vhea
+            0x00, 0x01, 0x10, 0x00, // version: 1.1
+            0x03, 0x84, // ascender: 900
+            0xFF, 0x9C, // descender: -100
+            0x00, 0x00, // line gap: 0
+            0x00, 0xC8, // advance height max: 200
+            0x00, 0x00, // min top side bearing: 0
+            0x00, 0x00, // min bottom side bearing: 0
+            0x00, 0x00, // y max extent: 0
+            0x00, 0x01, // caret slope rise: 1
+            0x00, 0x00, // caret slope run: 0
+            0x00, 0x00, // caret offset: 0
+            0x00, 0x00, 0x00, 0x00, // reserved
+            0x00, 0x00, 0x00, 0x00, // reserved
+            0x00, 0x00, // metric data format: 0
+            0x00, 0x02, // number of long vertical metrics: 2
+        ];
+        // Zeroed `head` and `hhea` tables.
+        data.extend_from_slice(&[0; 54]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.vertical_ascender(), Some(900));
+        assert_eq!(face.vertical_descender(), Some(-100));
+        assert_eq!(face.glyph_ver_advance(GlyphId(0)), Some(100));
+        assert_eq!(face.glyph_ver_advance(GlyphId(1)), Some(200));
+        // The last advance is repeated for glyphs without a long vertical metric.
+        assert_eq!(face.glyph_ver_advance(GlyphId(2)), Some(200));
+        assert_eq!(face.glyph_ver_advance(GlyphId(3)), Some(200));
+        assert_eq!(face.glyph_ver_side_bearing(GlyphId(3)), Some(40));
+        assert_eq!(face.glyph_ver_advance(GlyphId(4)), None);
+    }
+
+    #[test]
+    fn script_metrics() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.subscript_metrics(),
+                   Some(ScriptMetrics { x_size: 650, y_size: 600, x_offset: 0, y_offset: 75 }));
+        assert_eq!(face.superscript_metrics(),
+                   Some(ScriptMetrics { x_size: 650, y_size: 600, x_offset: 0, y_offset: 350 }));
+    }
+
+    #[test]
+    fn variation_instance_cache() {
+        let mut data = std::fs::read("benches/fonts/SourceSansVariable-Roman.otf").unwrap();
+        // `HVAR` has no LSB mapping, so reuse the advance one to get side bearing deltas.
+        let hvar = table_offset(&data, b"HVAR");
+        let advance_mapping = [data[hvar + 8], data[hvar + 9], data[hvar + 10], data[hvar + 11]];
+        data[hvar + 12..hvar + 16].copy_from_slice(&advance_mapping);
+        let mut face = Face::from_slice(&data, 0).unwrap();
+        let default_bearing = face.glyph_hor_side_bearing(GlyphId(10));
+        face.set_variation(Tag::from_bytes(b"wght"), 650.0).unwrap();
+        assert_ne!(face.glyph_hor_side_bearing(GlyphId(10)), default_bearing);
+        let cache = face.variation_instance_cache();
+
+        for id in 0..face.number_of_glyphs() {
+            let id = GlyphId(id);
+
+            let mut uncached = Builder(std::string::String::new());
+            let mut cached = Builder(std::string::String::new());
+            let bbox = face.outline_glyph(id, &mut uncached);
+            assert_eq!(face.outline_glyph_cached(id, &mut cached, &cache), bbox);
+            assert_eq!(cached.0, uncached.0);
+
+            assert_eq!(face.glyph_hor_advance_cached(id, &cache), face.glyph_hor_advance(id));
+            assert_eq!(face.glyph_ver_advance_cached(id, &cache), face.glyph_ver_advance(id));
+            assert_eq!(face.glyph_hor_side_bearing_cached(id, &cache), face.glyph_hor_side_bearing(id));
+            assert_eq!(face.glyph_ver_side_bearing_cached(id, &cache), face.glyph_ver_side_bearing(id));
+        }
+
+        // A stale cache is ignored.
+        let a = face.glyph_hor_advance(GlyphId(10));
+        face.set_variation(Tag::from_bytes(b"wght"), 200.0).unwrap();
+        assert_ne!(face.glyph_hor_advance(GlyphId(10)), a);
+        assert_eq!(face.glyph_hor_advance_cached(GlyphId(10), &cache),
+                   face.glyph_hor_advance(GlyphId(10)));
+    }
+
+    #[test]
+    fn avar_segment_maps() {
+        let data = std::fs::read("benches/fonts/SourceSansVariable-Roman.otf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut maps = face.avar_segment_maps();
+        let map: std::vec::Vec<_> = maps.next().unwrap().map(|(a, b)| (a.0, b.0)).collect();
+        assert_eq!(map, vec![
+            (-16384, -16384),
+            (0, 0),
+            (2341, 1638),
+            (4681, 6029),
+            (9362, 9830),
+            (11703, 13500),
+            (16384, 16384),
+        ]);
+        assert!(maps.next().is_none());
+
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert!(face.avar_segment_maps().next().is_none());
+    }
+
+    #[test]
+    fn style_flags() {
+        let mut data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        // Mark as Bold Italic in `head.macStyle`, while `OS/2.fsSelection` is Regular.
+        let head = table_offset(&data, b"head");
+        data[head + 44..head + 46].copy_from_slice(&[0x00, 0x03]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert!(face.is_regular());
+        assert!(!face.is_bold());
+        assert!(!face.is_italic());
+
+        // Hide OS/2 table by renaming it.
+        let record = table_record_offset(&data, b"OS/2");
+        data[record..record + 4].copy_from_slice(b"OS/3");
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert!(!face.is_regular());
+        assert!(face.is_bold());
+        assert!(face.is_italic());
+    }
+
+    #[test]
+    fn notdef_outline() {
+        for path in &["fonts/SourceSansPro-Regular-Tiny.ttf",
+                      "benches/fonts/SourceSansPro-Regular.otf"] {
+            let data = std::fs::read(path).unwrap();
+            let face = Face::from_slice(&data, 0).unwrap();
+            let mut builder = Builder(std::string::String::new());
+            let bbox = face.notdef_outline(&mut builder);
+            assert!(bbox.is_some());
+            assert!(!builder.0.is_empty());
+            assert_eq!(bbox, face.outline_glyph(GlyphId(0), &mut Builder(std::string::String::new())));
+        }
+
+        // Make `.notdef` empty by setting its end offset in the short `loca` to zero.
+        let mut data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let loca = table_offset(&data, b"loca");
+        data[loca + 2..loca + 4].copy_from_slice(&[0x00, 0x00]);
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut builder = Builder(std::string::String::new());
+        assert_eq!(face.notdef_outline(&mut builder), None);
+        assert!(builder.0.is_empty());
+    }
+
+    #[test]
+    fn glyph_index_mac_roman() {
+        let mut data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        // Change all `cmap` encoding records to Macintosh Roman.
+        let cmap = table_offset(&data, b"cmap");
+        let count = usize::from(u16::from_be_bytes([data[cmap + 2], data[cmap + 3]]));
+        for record in 0..count {
+            let offset = cmap + 4 + record * 8;
+            data[offset..offset + 4].copy_from_slice(&[0x00, 0x01, 0x00, 0x00]);
+        }
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert!(face.character_mapping_subtables().all(|e| e.is_mac_roman()));
+        assert_eq!(face.glyph_index('a'), Some(GlyphId(28)));
+        assert_eq!(face.glyph_index('Ж'), None);
+    }
+
+    #[test]
+    fn glyph_total_contours() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        // A simple glyph.
+        assert_eq!(face.glyph_total_contours(GlyphId(2)), Some(2));
+        // Two simple components with 2 and 1 contours.
+        assert_eq!(face.glyph_total_contours(GlyphId(54)), Some(3));
+        // A simple component and a composite one with a single contour.
+        assert_eq!(face.glyph_total_contours(GlyphId(63)), Some(3));
+        // A composite glyph with a single composite component.
+        assert_eq!(face.glyph_total_contours(GlyphId(98)), Some(2));
+        // An empty glyph.
+        assert_eq!(face.glyph_total_contours(GlyphId(1)), Some(0));
+        assert_eq!(face.glyph_total_contours(GlyphId(500)), None);
+    }
+
+    #[test]
+    fn units_per_em_is_power_of_two() {
+        let mut data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.units_per_em(), Some(1000));
+        assert!(!face.units_per_em_is_power_of_two());
+
+        let head = table_offset(&data, b"head");
+        data[head + 18..head + 20].copy_from_slice(&[0x08, 0x00]); // units per EM: 2048
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.units_per_em(), Some(2048));
+        assert!(face.units_per_em_is_power_of_two());
+    }
+
+    #[test]
+    fn caret_slope() {
+        let mut data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.caret_slope(), CaretSlope { rise: 1, run: 0, offset: 0 });
+
+        // Set caretSlopeRise, caretSlopeRun and caretOffset to a 12 degrees italic caret.
+        let hhea = table_offset(&data, b"hhea");
+        data[hhea + 18..hhea + 24].copy_from_slice(&[
+            0x03, 0xE8, // rise: 1000
+            0x00, 0xD5, // run: 213
+            0xFF, 0xEC, // offset: -20
+        ]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.caret_slope(), CaretSlope { rise: 1000, run: 213, offset: -20 });
+    }
+
+    #[test]
+    fn caret_slope_variation() {
+        let mut data = std::fs::read("benches/fonts/SourceSansVariable-Roman.otf").unwrap();
+
+        // The font has only `stro` and `xhgt` MVAR records,
+        // so reuse their deltas for the caret slope.
+        let (stro_delta, xhgt_delta) = {
+            let mut face = Face::from_slice(&data, 0).unwrap();
+            let default = (face.strikeout_metrics().unwrap().position, face.x_height().unwrap());
+            face.set_variation(Tag::from_bytes(b"wght"), 900.0).unwrap();
+            (face.strikeout_metrics().unwrap().position - default.0,
+             face.x_height().unwrap() - default.1)
+        };
+        assert!(stro_delta != 0 && xhgt_delta != 0);
+
+        // Value records are sorted by tag and `hcrn` < `hcrs`.
+        let mvar = table_offset(&data, b"MVAR");
+        let record_size = usize::from(u16::from_be_bytes([data[mvar + 6], data[mvar + 7]]));
+        data[mvar + 12..mvar + 16].copy_from_slice(b"hcrn");
+        data[mvar + 12 + record_size..mvar + 16 + record_size].copy_from_slice(b"hcrs");
+
+        let mut face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.caret_slope(), CaretSlope { rise: 1, run: 0, offset: 0 });
+        face.set_variation(Tag::from_bytes(b"wght"), 900.0).unwrap();
+        assert_eq!(face.caret_slope(), CaretSlope { rise: 1 + xhgt_delta, run: stro_delta, offset: 0 });
+    }
+
+    #[test]
+    fn head_dates() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.created(), LongDateTime(3630014625));
+        assert_eq!(face.modified(), LongDateTime(3630043433));
+        assert!(face.created() < face.modified());
+    }
+
+    #[test]
+    fn glyph_contour_endpoints() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+
+        // 'o'
+        let endpoints = face.glyph_contour_endpoints(GlyphId(42)).unwrap();
+        assert_eq!(endpoints.into_iter().collect::<std::vec::Vec<_>>(), vec![15, 27]);
+
+        // 'l'
+        assert_eq!(face.glyph_contour_endpoints(GlyphId(39)).unwrap().len(), 1);
+        // A composite 'i'.
+        assert!(face.glyph_contour_endpoints(GlyphId(36)).is_none());
+    }
+
+    #[test]
+    fn glyph_has_overlap_flag() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert!(!face.glyph_has_overlap_flag(GlyphId(13)));
+    }
+
+    #[test]
+    fn glyph_index_noncharacters() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.glyph_index('\u{FFFF}'), None);
+        assert_eq!(face.glyph_index('\u{FFFE}'), None);
+        assert!(face.glyph_index('A').is_some());
+    }
+
+    #[test]
+    fn gsub_debug_apply() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let gsub = face.substitution_table().unwrap();
+
+        // The font has only `f_f`, `f_t` and `f_f_t` ligatures.
+        let f = face.glyph_index('f').unwrap();
+        let t = face.glyph_index('t').unwrap();
+        let l = face.glyph_index('l').unwrap();
+        let liga = Tag::from_bytes(b"liga");
+        assert_eq!(gsub.debug_apply(&[f, t], liga), vec![GlyphId(84)]);
+        assert_eq!(gsub.debug_apply(&[f, f, t], liga), vec![GlyphId(85)]);
+        assert_eq!(gsub.debug_apply(&[f, t], Tag::from_bytes(b"dlig")), vec![f, t]);
+        assert_eq!(gsub.debug_apply(&[t, f], liga), vec![t, f]);
+        assert_eq!(gsub.debug_apply(&[l, f, t], liga), vec![l, GlyphId(84)]);
+    }
+
+    #[test]
+    fn raster_image_font_units() {
+        // A 109x128 emoji at 128 ppem in a 2048 units per em font.
+        let image = RasterGlyphImage {
+            x: 0,
+            y: -14,
+            width: 109,
+            height: 128,
+            pixels_per_em: 128,
+            format: RasterImageFormat::PNG,
+            data: &[],
+        };
+
+        assert_eq!(image.origin_in_font_units(2048), Some((0.0, -224.0)));
+        assert_eq!(image.origin_in_font_units(0), None);
+
+        let image = RasterGlyphImage { pixels_per_em: 0, ..image };
+        assert_eq!(image.origin_in_font_units(2048), None);
+    }
+
+    #[test]
+    fn glyph_raster_image() {
+        let mut data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let (number_of_glyphs, glyph_id) = {
+            let face = Face::from_slice(&data, 0).unwrap();
+            (face.number_of_glyphs(), face.glyph_index('A').unwrap())
+        };
+
+        // A PNG signature and an IHDR chunk header, which is enough to get the image size.
+        let png = &[
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, // signature
+            0x00, 0x00, 0x00, 0x0D, // IHDR length: 13
+            0x49, 0x48, 0x44, 0x52, // IHDR
+            0x00, 0x00, 0x00, 0x6D, // width: 109
+            0x00, 0x00, 0x00, 0x80, // height: 128
+        ];
+
+        let mut sbix = vec![
+            0x00, 0x01, // version: 1
+            0x00, 0x01, // flags
+            0x00, 0x00, 0x00, 0x01, // number of strikes: 1
+            0x00, 0x00, 0x00, 0x0C, // strike offset [0]: 12
+            // Strike
+            0x00, 0x80, // ppem: 128
+            0x00, 0x48, // ppi: 72
+        ];
+        // Glyph data offsets are from the start of the strike.
+        let glyph_data_offset = 4 + (u32::from(number_of_glyphs) + 1) * 4;
+        let glyph_data_len = 8 + png.len() as u32;
+        for i in 0..=number_of_glyphs {
+            let offset = if i <= glyph_id.0 {
+                glyph_data_offset
+            } else {
+                glyph_data_offset + glyph_data_len
+            };
+            sbix.extend_from_slice(&offset.to_be_bytes());
+        }
+        sbix.extend_from_slice(&[
+            0x00, 0x00, // x: 0
+            0xFF, 0xF2, // y: -14
+            0x70, 0x6E, 0x67, 0x20, // graphic type: png
+        ]);
+        sbix.extend_from_slice(png);
+
+        // Replace the post table record, which keeps the table records sorted.
+        replace_table(&mut data, b"post", b"sbix", &sbix);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        let image = face.glyph_raster_image(glyph_id, 128).unwrap();
+        assert_eq!((image.width, image.height, image.pixels_per_em), (109, 128, 128));
+        assert_eq!((image.x, image.y), (0, -14));
+        assert_eq!(face.units_per_em(), Some(1000));
+        assert_eq!(image.origin_in_font_units(1000), Some((0.0, -14.0 * 1000.0 / 128.0)));
+        assert!(face.glyph_raster_image(GlyphId(glyph_id.0 + 1), 128).is_none());
+
+        // An invalid units per em doesn't prevent getting an image.
+        let head = table_offset(&data, b"head");
+        data[head + 18..head + 20].copy_from_slice(&[0x00, 0x00]);
+        let face = Face::from_slice(&data, 0).unwrap();
+        let image = face.glyph_raster_image(glyph_id, 128).unwrap();
+        assert_eq!((image.x, image.y), (0, -14));
+        assert_eq!(face.units_per_em(), None);
+    }
+
+    #[test]
+    fn glyph_in_layout() {
+        let data = std::fs::read("fonts/SourceSansPro-Regular-Tiny.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        // A ligature glyph.
+        assert!(face.glyph_in_layout(GlyphId(85)));
+        assert!(face.glyph_in_layout(face.glyph_index('f').unwrap()));
+        assert!(!face.glyph_in_layout(GlyphId(0)));
+    }
 }
@@ -73,6 +73,24 @@ impl FromData for i32 {
     }
 }
 
+impl FromData for u64 {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        data.try_into().ok().map(u64::from_be_bytes)
+    }
+}
+
+impl FromData for i64 {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        data.try_into().ok().map(i64::from_be_bytes)
+    }
+}
+
 /// A u24 number.
 ///
 /// Stored as u32, but encoded as 3 bytes in the font.
@@ -93,7 +111,9 @@ impl FromData for U24 {
 
 
 /// A 16-bit signed fixed number with the low 14 bits of fraction (2.14).
-#[derive(Clone, Copy, Debug)]
+///
+/// Compared using the underlying `i16`, which preserves the numeric order.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct F2DOT14(pub i16);
 
 impl F2DOT14 {
@@ -102,6 +122,27 @@ impl F2DOT14 {
     pub fn to_f32(&self) -> f32 {
         f32::from(self.0) / 16384.0
     }
+
+    /// Creates a value from f32.
+    ///
+    /// The value is clamped to the `[-2.0, 2.0 - 1/16384]` range
+    /// and rounded to the nearest 2.14 value. NaN is converted to zero.
+    #[inline]
+    pub fn from_f32(v: f32) -> Self {
+        let n = v * 16384.0;
+        // Float to int casts are not saturating in Rust < 1.45, so we have to check manually.
+        if n.is_nan() {
+            F2DOT14(0)
+        } else if n >= 32767.0 {
+            F2DOT14(32767)
+        } else if n <= -32768.0 {
+            F2DOT14(-32768)
+        } else {
+            // We can't use `round()` in `no_std`, so this is the next best thing.
+            let n = if n >= 0.0 { n + 0.5 } else { n - 0.5 };
+            F2DOT14(n as i16)
+        }
+    }
 }
 
 impl FromData for F2DOT14 {
@@ -115,27 +156,86 @@ impl FromData for F2DOT14 {
 
 
 /// A 32-bit signed fixed-point number (16.16).
-#[derive(Clone, Copy, Debug)]
-pub struct Fixed(pub f32);
+///
+/// Stores the raw value, so two numbers with the same bits are always equal.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Fixed(i32);
+
+impl Fixed {
+    /// Creates a value from raw 16.16 bits.
+    #[inline]
+    pub fn from_raw(n: i32) -> Self {
+        Fixed(n)
+    }
+
+    /// Returns raw 16.16 bits.
+    #[inline]
+    pub fn raw(&self) -> i32 {
+        self.0
+    }
+
+    /// Creates a value from f32.
+    ///
+    /// The value is rounded to the nearest 16.16 value, i.e. the resolution is 1/65536.
+    /// Out of range values are clamped. NaN is converted to zero.
+    #[inline]
+    pub fn from_f32(v: f32) -> Self {
+        let n = v * 65536.0;
+        // Float to int casts are not saturating in Rust < 1.45, so we have to check manually.
+        if n.is_nan() {
+            Fixed(0)
+        } else if n >= 2147483647.0 {
+            Fixed(2147483647)
+        } else if n <= -2147483648.0 {
+            Fixed(-2147483648)
+        } else {
+            // We can't use `round()` in `no_std`, so this is the next best thing.
+            let n = if n >= 0.0 { n + 0.5 } else { n - 0.5 };
+            Fixed(n as i32)
+        }
+    }
+
+    /// Converts 16.16 to f32.
+    #[inline]
+    pub fn to_f32(&self) -> f32 {
+        // TODO: is it safe to cast?
+        self.0 as f32 / 65536.0
+    }
+}
 
 impl FromData for Fixed {
     const SIZE: usize = 4;
 
     #[inline]
     fn parse(data: &[u8]) -> Option<Self> {
-        // TODO: is it safe to cast?
-        i32::parse(data).map(|n| Fixed(n as f32 / 65536.0))
+        i32::parse(data).map(Fixed)
     }
 }
 
 
-/// A safe u32 to usize casting.
+/// A date represented in number of seconds since 12:00 midnight, January 1, 1904 in GMT/UTC.
+///
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/otff#data-types
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct LongDateTime(pub i64);
+
+impl FromData for LongDateTime {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        i64::parse(data).map(LongDateTime)
+    }
+}
+
+
+/// A safe unsigned integer to usize casting.
 ///
 /// Rust doesn't implement `From<u32> for usize`,
 /// because it has to support 16 bit targets.
 /// We don't, so we can allow this.
 pub trait NumFrom<T>: Sized {
-    /// Converts u32 into usize.
+    /// Converts an unsigned integer into usize.
     fn num_from(_: T) -> Self;
 }
 
@@ -151,6 +251,20 @@ impl NumFrom<u32> for usize {
     }
 }
 
+impl NumFrom<u16> for usize {
+    #[inline]
+    fn num_from(v: u16) -> Self {
+        usize::from(v)
+    }
+}
+
+impl NumFrom<u8> for usize {
+    #[inline]
+    fn num_from(v: u8) -> Self {
+        usize::from(v)
+    }
+}
+
 
 /// Just like TryFrom<N>, but for numeric types not supported by the Rust's std.
 pub trait TryNumFrom<T>: Sized {
@@ -201,6 +315,36 @@ impl TryNumFrom<f32> for i32 {
     }
 }
 
+impl TryNumFrom<f64> for i16 {
+    #[inline]
+    fn try_num_from(v: f64) -> Option<Self> {
+        i32::try_num_from(v).and_then(|v| i16::try_from(v).ok())
+    }
+}
+
+impl TryNumFrom<f64> for u16 {
+    #[inline]
+    fn try_num_from(v: f64) -> Option<Self> {
+        i32::try_num_from(v).and_then(|v| u16::try_from(v).ok())
+    }
+}
+
+impl TryNumFrom<f64> for i32 {
+    #[inline]
+    fn try_num_from(v: f64) -> Option<Self> {
+        // Float as int truncates toward zero, so we want to allow values
+        // in the exclusive range `(MIN-1, MAX+1)`.
+        // Unlike f32, f64 can represent both boundaries exactly.
+        const MIN_M1: f64 = -2147483649.0;
+        const MAX_P1: f64 = 2147483648.0;
+        if v > MIN_M1 && v < MAX_P1 {
+            Some(v as i32)
+        } else {
+            None
+        }
+    }
+}
+
 
 /// A slice-like container that converts internal binary data only on access.
 ///
@@ -253,21 +397,36 @@ impl<'a, T: FromData> LazyArray16<'a, T> {
         }
     }
 
-    /// Returns array's length.
+    /// Returns a subarray.
+    ///
+    /// Returns `None` when `range` is out of bounds or reversed.
+    /// Trailing bytes that do not form a whole element are never included.
     #[inline]
     pub fn slice(&self, range: Range<u16>) -> Option<Self> {
+        if range.start > range.end || range.end > self.len() {
+            return None;
+        }
+
         let start = usize::from(range.start) * T::SIZE;
         let end = usize::from(range.end) * T::SIZE;
-        Some(LazyArray16 {
-            data: self.data.get(start..end)?,
-            ..LazyArray16::default()
-        })
+        Some(LazyArray16::new(self.data.get(start..end)?))
+    }
+
+    /// Returns a subarray of `count` elements starting at `start`.
+    ///
+    /// Same as `slice(start..start + count)`, but checks for overflow.
+    /// Returns `None` when the subarray is out of bounds.
+    #[inline]
+    pub fn subarray(&self, start: u16, count: u16) -> Option<Self> {
+        self.slice(start..start.checked_add(count)?)
     }
 
     /// Returns array's length.
+    ///
+    /// Saturates at 65535 when the underlying data holds more elements.
     #[inline]
     pub fn len(&self) -> u16 {
-        (self.data.len() / T::SIZE) as u16
+        u16::try_from(self.data.len() / T::SIZE).unwrap_or(0xFFFF)
     }
 
     /// Checks if array is empty.
@@ -276,6 +435,14 @@ impl<'a, T: FromData> LazyArray16<'a, T> {
         self.len() == 0
     }
 
+    /// Returns an iterator over array's items.
+    ///
+    /// Same as `into_iter`, but doesn't consume the array.
+    #[inline]
+    pub fn iter(&self) -> LazyArrayIter16<'a, T> {
+        LazyArray16::new(self.data).into_iter()
+    }
+
     /// Performs a binary search by specified `key`.
     #[inline]
     pub fn binary_search(&self, key: &T) -> Option<(u16, T)>
@@ -284,6 +451,14 @@ impl<'a, T: FromData> LazyArray16<'a, T> {
         self.binary_search_by(|p| p.cmp(key))
     }
 
+    /// Performs a binary search by a `key` extracted using specified closure.
+    #[inline]
+    pub fn binary_search_by_key<B, F>(&self, key: &B, mut f: F) -> Option<(u16, T)>
+        where B: Ord, F: FnMut(&T) -> B
+    {
+        self.binary_search_by(|p| f(p).cmp(key))
+    }
+
     /// Performs a binary search using specified closure.
     #[inline]
     pub fn binary_search_by<F>(&self, mut f: F) -> Option<(u16, T)>
@@ -314,6 +489,44 @@ impl<'a, T: FromData> LazyArray16<'a, T> {
         let value = self.get(base)?;
         if f(&value) == Ordering::Equal { Some((base, value)) } else { None }
     }
+
+    /// Returns the index of the first element for which `pred` returns `false`.
+    ///
+    /// The array must be partitioned by `pred`, i.e. all elements for which it returns `true`
+    /// must precede all elements for which it returns `false`.
+    /// Returns `len()` when `pred` returns `true` for all elements.
+    #[inline]
+    pub fn partition_point<F>(&self, mut pred: F) -> u16
+        where F: FnMut(&T) -> bool
+    {
+        let mut left = 0;
+        let mut right = self.len();
+        while left < right {
+            // No overflow, since `left < right <= u16::MAX`.
+            let mid = left + (right - left) / 2;
+            match self.get(mid) {
+                Some(ref value) if pred(value) => left = mid + 1,
+                _ => right = mid,
+            }
+        }
+
+        left
+    }
+
+    /// Returns a range of all elements for which `f` returns `Ordering::Equal`.
+    ///
+    /// The array must be sorted according to `f`.
+    /// Returns an empty range when there are no matching elements.
+    #[inline]
+    pub fn equal_range_by<F>(&self, mut f: F) -> Range<u16>
+        where F: FnMut(&T) -> core::cmp::Ordering
+    {
+        use core::cmp::Ordering;
+
+        let start = self.partition_point(|v| f(v) == Ordering::Less);
+        let end = self.partition_point(|v| f(v) != Ordering::Greater);
+        start..end
+    }
 }
 
 impl<'a, T: FromData + core::fmt::Debug + Copy> core::fmt::Debug for LazyArray16<'a, T> {
@@ -359,9 +572,26 @@ impl<'a, T: FromData> Iterator for LazyArrayIter16<'a, T> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.index += 1; // TODO: check
+        if self.index >= self.data.len() {
+            return None;
+        }
+
+        self.index += 1;
         self.data.get(self.index - 1)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: FromData> ExactSizeIterator for LazyArrayIter16<'a, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        usize::from(self.data.len().saturating_sub(self.index))
+    }
 }
 
 
@@ -406,10 +636,36 @@ impl<'a, T: FromData> LazyArray32<'a, T> {
         }
     }
 
+    /// Returns the last value.
+    #[inline]
+    pub fn last(&self) -> Option<T> {
+        if !self.is_empty() {
+            self.get(self.len() - 1)
+        } else {
+            None
+        }
+    }
+
     /// Returns array's length.
+    ///
+    /// Saturates at 4294967295 when the underlying data holds more elements.
     #[inline]
     pub fn len(&self) -> u32 {
-        (self.data.len() / T::SIZE) as u32
+        u32::try_from(self.data.len() / T::SIZE).unwrap_or(0xFFFF_FFFF)
+    }
+
+    /// Checks if array is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over array's items.
+    ///
+    /// Same as `into_iter`, but doesn't consume the array.
+    #[inline]
+    pub fn iter(&self) -> LazyArrayIter32<'a, T> {
+        LazyArray32::new(self.data).into_iter()
     }
 
     /// Performs a binary search by specified `key`.
@@ -420,6 +676,14 @@ impl<'a, T: FromData> LazyArray32<'a, T> {
         self.binary_search_by(|p| p.cmp(key))
     }
 
+    /// Performs a binary search by a `key` extracted using specified closure.
+    #[inline]
+    pub fn binary_search_by_key<B, F>(&self, key: &B, mut f: F) -> Option<(u32, T)>
+        where B: Ord, F: FnMut(&T) -> B
+    {
+        self.binary_search_by(|p| f(p).cmp(key))
+    }
+
     /// Performs a binary search using specified closure.
     #[inline]
     pub fn binary_search_by<F>(&self, mut f: F) -> Option<(u32, T)>
@@ -485,13 +749,30 @@ impl<'a, T: FromData> Iterator for LazyArrayIter32<'a, T> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.index += 1; // TODO: check
+        if self.index >= self.data.len() {
+            return None;
+        }
+
+        self.index += 1;
         self.data.get(self.index - 1)
     }
 
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
     #[inline]
     fn count(self) -> usize {
-        usize::num_from(self.data.len())
+        self.len()
+    }
+}
+
+impl<'a, T: FromData> ExactSizeIterator for LazyArrayIter32<'a, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        usize::num_from(self.data.len().saturating_sub(self.index))
     }
 }
 
@@ -542,6 +823,25 @@ impl<'a> Stream<'a> {
         self.offset
     }
 
+    /// Returns the number of bytes left in the stream.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.offset)
+    }
+
+    /// Sets the current offset.
+    ///
+    /// Returns `None` and leaves the offset unchanged when `offset` is out of bounds.
+    #[inline]
+    pub fn set_offset(&mut self, offset: usize) -> Option<()> {
+        if offset <= self.data.len() {
+            self.offset = offset;
+            Some(())
+        } else {
+            None
+        }
+    }
+
     /// Returns the trailing data.
     ///
     /// Returns `None` when `Stream` is reached the end.
@@ -569,7 +869,8 @@ impl<'a> Stream<'a> {
     /// Advances by the specified `len` and checks for bounds.
     #[inline]
     pub fn advance_checked(&mut self, len: usize) -> Option<()> {
-        if self.offset + len <= self.data.len() {
+        // `len` can come from font data, so the sum can overflow.
+        if self.offset.checked_add(len)? <= self.data.len() {
             self.advance(len);
             Some(())
         } else {
@@ -589,30 +890,63 @@ impl<'a> Stream<'a> {
     /// Parses the type from the steam at offset.
     #[inline]
     pub fn read_at<T: FromData>(data: &[u8], offset: usize) -> Option<T> {
-        data.get(offset..offset + T::SIZE).and_then(T::parse)
+        data.get(offset..offset.checked_add(T::SIZE)?).and_then(T::parse)
     }
 
     /// Reads N bytes from the stream.
     #[inline]
     pub fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
-        let v = self.data.get(self.offset..self.offset + len)?;
+        let v = self.data.get(self.offset..self.offset.checked_add(len)?)?;
         self.advance(len);
         Some(v)
     }
 
+    /// Checks that the next `count` types can be read as a slice.
+    ///
+    /// When `true`, `read_array16` with the same `count` will not fail.
+    #[inline]
+    pub fn array16_fits<T: FromData>(&self, count: u16) -> bool {
+        match usize::from(count).checked_mul(T::SIZE) {
+            Some(len) => len <= self.remaining(),
+            None => false,
+        }
+    }
+
     /// Reads the next `count` types as a slice.
     #[inline]
     pub fn read_array16<T: FromData>(&mut self, count: u16) -> Option<LazyArray16<'a, T>> {
-        let len = usize::from(count) * T::SIZE;
+        let len = usize::from(count).checked_mul(T::SIZE)?;
         self.read_bytes(len).map(LazyArray16::new)
     }
 
+    /// Parses an array of `count` types from the data at offset.
+    #[inline]
+    pub fn read_array16_at<T: FromData>(
+        data: &'a [u8],
+        offset: usize,
+        count: u16,
+    ) -> Option<LazyArray16<'a, T>> {
+        let end = offset.checked_add(usize::from(count).checked_mul(T::SIZE)?)?;
+        data.get(offset..end).map(LazyArray16::new)
+    }
+
     /// Reads the next `count` types as a slice.
     #[inline]
     pub fn read_array32<T: FromData>(&mut self, count: u32) -> Option<LazyArray32<'a, T>> {
-        let len = usize::num_from(count) * T::SIZE;
+        let len = usize::num_from(count).checked_mul(T::SIZE)?;
         self.read_bytes(len).map(LazyArray32::new)
     }
+
+    /// Reads the next `count` offsets, which are relative to `data`.
+    #[inline]
+    pub fn read_offsets16<T: Offset + FromData>(
+        &mut self,
+        count: u16,
+        data: &'a [u8],
+    ) -> Option<Offsets16<'a, T>> {
+        let offsets = self.read_array16(count)?;
+        Some(Offsets16 { data, offsets })
+    }
 }
 
 
@@ -623,6 +957,11 @@ pub trait Offset {
 
     /// Checks that offset is null.
     fn is_null(&self) -> bool { self.to_usize() == 0 }
+
+    /// Checks that offset points inside data with the specified length.
+    ///
+    /// An offset equal to `data_len` fits, since it points to empty data.
+    fn fits(&self, data_len: usize) -> bool { self.to_usize() <= data_len }
 }
 
 
@@ -657,6 +996,37 @@ impl FromData for Option<Offset16> {
 }
 
 
+/// A type-safe u24 offset.
+#[derive(Clone, Copy, Debug)]
+pub struct Offset24(pub U24);
+
+impl Offset for Offset24 {
+    #[inline]
+    fn to_usize(&self) -> usize {
+        usize::num_from(self.0 .0)
+    }
+}
+
+impl FromData for Offset24 {
+    const SIZE: usize = U24::SIZE;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        U24::parse(data).map(Offset24)
+    }
+}
+
+impl FromData for Option<Offset24> {
+    const SIZE: usize = Offset24::SIZE;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let offset = Offset24::parse(data)?;
+        if offset.0 .0 != 0 { Some(Some(offset)) } else { Some(None) }
+    }
+}
+
+
 /// A type-safe u32 offset.
 #[derive(Clone, Copy, Debug)]
 pub struct Offset32(pub u32);
@@ -689,6 +1059,158 @@ impl FromData for Option<Offset32> {
 }
 
 
+/// An array of offsets with the data they are relative to.
+///
+/// This is a low-level, internal structure that should not be used directly.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Offsets16<'a, T: Offset + FromData> {
+    data: &'a [u8],
+    offsets: LazyArray16<'a, T>,
+}
+
+impl<T: Offset + FromData> Default for Offsets16<'_, T> {
+    #[inline]
+    fn default() -> Self {
+        Offsets16 {
+            data: &[],
+            offsets: LazyArray16::default(),
+        }
+    }
+}
+
+impl<'a, T: Offset + FromData> Offsets16<'a, T> {
+    /// Returns the number of offsets, including the null ones.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.offsets.len()
+    }
+
+    /// Checks if there are no offsets.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Returns the data at offset with the specified `index`.
+    ///
+    /// Returns `None` when the offset is null or out of bounds.
+    #[inline]
+    pub fn get(&self, index: u16) -> Option<&'a [u8]> {
+        let offset = self.offsets.get(index)?;
+        if offset.is_null() {
+            return None;
+        }
+
+        self.data.get(offset.to_usize()..)
+    }
+
+    /// Returns the data between offsets with the specified indices.
+    ///
+    /// Unlike `get`, which returns the data up to the end, allows bounding a subtable
+    /// by the next one.
+    ///
+    /// Returns `None` when any of the offsets is null or out of bounds
+    /// or when the offset at `index` is greater than the one at `next_index`.
+    #[inline]
+    pub fn slice_to(&self, index: u16, next_index: u16) -> Option<&'a [u8]> {
+        let start = self.offsets.get(index)?;
+        let end = self.offsets.get(next_index)?;
+        if start.is_null() || end.is_null() {
+            return None;
+        }
+
+        self.data.get(start.to_usize()..end.to_usize())
+    }
+
+    /// Returns an iterator over data at offsets along with offset indices.
+    ///
+    /// Like the regular iterator, skips null and out of bounds offsets,
+    /// but the indices still reflect the original positions.
+    #[inline]
+    pub fn iter_indexed(self) -> OffsetsIndexedIter16<'a, T> {
+        OffsetsIndexedIter16 {
+            offsets: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, T: Offset + FromData> IntoIterator for Offsets16<'a, T> {
+    type Item = &'a [u8];
+    type IntoIter = OffsetsIter16<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        OffsetsIter16 {
+            offsets: self,
+            index: 0,
+        }
+    }
+}
+
+
+/// An iterator over `Offsets16`.
+///
+/// Null and out of bounds offsets are skipped.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct OffsetsIter16<'a, T: Offset + FromData> {
+    offsets: Offsets16<'a, T>,
+    index: u16,
+}
+
+impl<T: Offset + FromData> Default for OffsetsIter16<'_, T> {
+    #[inline]
+    fn default() -> Self {
+        Offsets16::default().into_iter()
+    }
+}
+
+impl<'a, T: Offset + FromData> Iterator for OffsetsIter16<'a, T> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.offsets.len() {
+            self.index += 1;
+            if let Some(data) = self.offsets.get(self.index - 1) {
+                return Some(data);
+            }
+        }
+
+        None
+    }
+}
+
+
+/// An iterator over `Offsets16` that yields offset indices as well.
+///
+/// Null and out of bounds offsets are skipped.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct OffsetsIndexedIter16<'a, T: Offset + FromData> {
+    offsets: Offsets16<'a, T>,
+    index: u16,
+}
+
+impl<'a, T: Offset + FromData> Iterator for OffsetsIndexedIter16<'a, T> {
+    type Item = (u16, &'a [u8]);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.offsets.len() {
+            self.index += 1;
+            if let Some(data) = self.offsets.get(self.index - 1) {
+                return Some((self.index - 1, data));
+            }
+        }
+
+        None
+    }
+}
+
+
 #[inline]
 pub(crate) fn i16_bound(min: i16, val: i16, max: i16) -> i16 {
     use core::cmp;
@@ -709,3 +1231,405 @@ pub(crate) fn f32_bound(min: f32, val: f32, max: f32) -> f32 {
 
     val
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f2dot14_ordering() {
+        let a = F2DOT14::parse(&[0x40, 0x00]).unwrap(); // 1.0
+        assert_eq!(a, F2DOT14(16384));
+        assert_eq!(a.to_f32(), 1.0);
+
+        let b = F2DOT14::parse(&[0xC0, 0x00]).unwrap(); // -1.0
+        assert!(b < F2DOT14(0));
+        assert!(b < a);
+        assert_eq!(a.cmp(&b), a.0.cmp(&b.0));
+        assert_eq!(F2DOT14(-1).max(F2DOT14(1)), F2DOT14(1));
+    }
+
+    #[test]
+    fn stream_read_array16_at() {
+        let data = &[0x00, 0x01, 0x00, 0x02, 0x00, 0x03];
+        let array = Stream::read_array16_at::<u16>(data, 2, 2).unwrap();
+        assert_eq!(array.into_iter().collect::<std::vec::Vec<_>>(), &[2, 3]);
+        assert_eq!(Stream::read_array16_at::<u16>(data, 6, 0).unwrap().len(), 0);
+        assert!(Stream::read_array16_at::<u16>(data, 2, 3).is_none());
+        assert!(Stream::read_array16_at::<u16>(data, 8, 0).is_none());
+        assert!(Stream::read_array16_at::<u16>(data, core::usize::MAX, 1).is_none());
+    }
+
+    #[test]
+    fn stream_array16_fits() {
+        let data = &[0x00, 0x01, 0x00, 0x02, 0x00, 0x03];
+        let mut s = Stream::new(data);
+        assert!(s.array16_fits::<u16>(3));
+        assert!(s.array16_fits::<u32>(1));
+        assert!(!s.array16_fits::<u32>(2));
+        assert!(!s.array16_fits::<u16>(0xFFFF));
+        assert!(s.array16_fits::<u16>(0));
+
+        s.skip::<u16>();
+        assert!(s.array16_fits::<u16>(2));
+        assert!(!s.array16_fits::<u16>(3));
+        assert!(s.read_array16::<u16>(3).is_none());
+        assert!(s.read_array16::<u16>(2).is_some());
+        assert!(!s.array16_fits::<u16>(1));
+    }
+
+    #[test]
+    fn stream_remaining() {
+        let mut s = Stream::new(&[0x00, 0x01, 0x00]);
+        assert_eq!(s.remaining(), 3);
+        s.skip::<u16>();
+        assert_eq!(s.remaining(), 1);
+        // `advance` doesn't check bounds.
+        s.advance(5);
+        assert!(s.at_end());
+        assert_eq!(s.remaining(), 0);
+    }
+
+    #[test]
+    fn lazy_array16_subarray() {
+        let array = LazyArray16::<u16>::new(&[0x00, 0x01, 0x00, 0x02, 0x00, 0x03]);
+        let sub = array.subarray(1, 2).unwrap();
+        assert_eq!(sub.len(), 2);
+        assert_eq!(sub.get(0), Some(2));
+        assert_eq!(sub.get(1), Some(3));
+        assert_eq!(array.subarray(3, 0).unwrap().len(), 0);
+        assert!(array.subarray(2, 2).is_none());
+        assert!(array.subarray(1, 0xFFFF).is_none());
+    }
+
+    #[test]
+    fn lazy_array16_len_saturates() {
+        let data = vec![0; 0x10001 * 2];
+        let array = LazyArray16::<u16>::new(&data);
+        assert_eq!(array.len(), 0xFFFF);
+        assert_eq!(array.get(0xFFFE), Some(0));
+        assert_eq!(array.into_iter().count(), 0xFFFF);
+    }
+
+    #[test]
+    fn stream_length_overflow() {
+        let mut s = Stream::new(&[0x00, 0x01, 0x00]);
+        s.skip::<u8>();
+        assert!(s.read_bytes(core::usize::MAX).is_none());
+        assert!(s.advance_checked(core::usize::MAX).is_none());
+        assert_eq!(s.offset(), 1);
+        assert!(Stream::read_at::<u16>(&[0x00, 0x01], core::usize::MAX).is_none());
+        assert_eq!(s.read::<u16>(), Some(256));
+    }
+
+    #[test]
+    fn array_length_overflow() {
+        // A type large enough to overflow the array length on any target.
+        struct Huge;
+
+        impl FromData for Huge {
+            const SIZE: usize = core::usize::MAX / 2 + 1;
+
+            fn parse(_: &[u8]) -> Option<Self> {
+                Some(Huge)
+            }
+        }
+
+        let data = &[0x00, 0x01, 0x00, 0x02];
+        let mut s = Stream::new(data);
+        assert!(s.read_array32::<u32>(0xFFFF_FFFF).is_none());
+        assert!(s.read_array32::<Huge>(2).is_none());
+        assert!(s.read_array16::<Huge>(2).is_none());
+        assert!(Stream::read_array16_at::<Huge>(data, 0, 2).is_none());
+        // A failed read doesn't advance the stream.
+        assert_eq!(s.read_array16::<u16>(2).map(|a| a.len()), Some(2));
+    }
+
+    #[test]
+    fn stream_set_offset() {
+        let mut s = Stream::new(&[0x00, 0x01, 0x00, 0x02]);
+        assert_eq!(s.read::<u16>(), Some(1));
+        let offset = s.offset();
+        assert_eq!(s.read::<u16>(), Some(2));
+        assert!(s.at_end());
+
+        assert_eq!(s.set_offset(offset), Some(()));
+        assert_eq!(s.read::<u16>(), Some(2));
+
+        // Seeking to the end is allowed, but not past it.
+        assert_eq!(s.set_offset(4), Some(()));
+        s.set_offset(0).unwrap();
+        assert_eq!(s.set_offset(5), None);
+        assert_eq!(s.offset(), 0);
+        assert_eq!(s.read::<u16>(), Some(1));
+    }
+
+    #[test]
+    fn offset24() {
+        let offset = Offset24::parse(&[0x01, 0x02, 0x03]).unwrap();
+        assert_eq!(offset.to_usize(), 0x010203);
+        assert!(!offset.is_null());
+
+        assert!(Option::<Offset24>::parse(&[0x00, 0x00, 0x00]).unwrap().is_none());
+        assert!(Option::<Offset24>::parse(&[0x00, 0x00, 0x01]).unwrap().is_some());
+        assert!(Option::<Offset24>::parse(&[0x00, 0x01]).is_none());
+
+        let data = &[
+            0x00, 0x00, 0x06, // offset [0]: 6
+            0x00, 0x00, 0x00, // offset [1]: NULL
+            0xAA, 0xBB, // data
+        ];
+        let mut s = Stream::new(data);
+        let offsets = s.read_offsets16::<Offset24>(2, data).unwrap();
+        assert_eq!(offsets.get(0), Some(&[0xAA, 0xBB][..]));
+        assert_eq!(offsets.get(1), None);
+    }
+
+    #[test]
+    fn offset_fits() {
+        assert!(Offset16(0).fits(0));
+        assert!(Offset16(4).fits(4));
+        assert!(!Offset16(5).fits(4));
+        assert!(Offset24(U24(0xFFFFFF)).fits(0x1000000));
+        assert!(!Offset32(0xFFFFFFFF).fits(0xFFFF));
+    }
+
+    #[test]
+    fn offsets_indexed() {
+        let data = &[
+            0x00, 0x00, // offset [0]: NULL
+            0x00, 0x0A, // offset [1]: 10
+            0x00, 0x00, // offset [2]: NULL
+            0x00, 0xFF, // offset [3]: 255 (out of bounds)
+            0x00, 0x0B, // offset [4]: 11
+            0xAA, 0xBB, // data
+        ];
+        let mut s = Stream::new(data);
+        let offsets = s.read_offsets16::<Offset16>(5, data).unwrap();
+        let items: std::vec::Vec<_> = offsets.iter_indexed().collect();
+        assert_eq!(items, vec![(1, &[0xAA, 0xBB][..]), (4, &[0xBB][..])]);
+        assert_eq!(offsets.into_iter().count(), 2);
+    }
+
+    #[test]
+    fn offsets_slice_to() {
+        let data = &[
+            0x00, 0x08, // offset [0]: 8
+            0x00, 0x0A, // offset [1]: 10
+            0x00, 0x00, // offset [2]: NULL
+            0x00, 0xFF, // offset [3]: 255 (out of bounds)
+            0xAA, 0xBB, 0xCC, // data
+        ];
+        let mut s = Stream::new(data);
+        let offsets = s.read_offsets16::<Offset16>(4, data).unwrap();
+        assert_eq!(offsets.slice_to(0, 1), Some(&[0xAA, 0xBB][..]));
+        assert_eq!(offsets.slice_to(1, 1), Some(&[][..]));
+        // Not monotonic.
+        assert_eq!(offsets.slice_to(1, 0), None);
+        assert_eq!(offsets.slice_to(0, 2), None);
+        assert_eq!(offsets.slice_to(0, 3), None);
+        assert_eq!(offsets.slice_to(0, 4), None);
+    }
+
+    #[test]
+    fn u64_and_i64() {
+        let data = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+        assert_eq!(u64::parse(data), Some(0x0123456789ABCDEF));
+        assert_eq!(i64::parse(data), Some(0x0123456789ABCDEF));
+
+        let data = &[0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10];
+        assert_eq!(u64::parse(data), Some(0xFEDCBA9876543210));
+        assert_eq!(i64::parse(data), Some(-0x0123456789ABCDF0));
+
+        let mut s = Stream::new(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xFF]);
+        assert_eq!(s.read::<u64>(), Some(42));
+        assert_eq!(s.read::<i64>(), None);
+    }
+
+    #[test]
+    fn long_date_time() {
+        // 2020-01-01T00:00:00Z
+        let date = LongDateTime::parse(&[0x00, 0x00, 0x00, 0x00, 0xDA, 0x31, 0x91, 0x80]).unwrap();
+        assert_eq!(date, LongDateTime(3660681600));
+
+        let date = LongDateTime::parse(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]).unwrap();
+        assert_eq!(date, LongDateTime(-1));
+
+        assert_eq!(LongDateTime::parse(&[0x00; 7]), None);
+    }
+
+    #[test]
+    fn partition_point() {
+        let array = LazyArray16::<u16>::new(&[0x00, 0x01, 0x00, 0x03, 0x00, 0x03, 0x00, 0x05]);
+        assert_eq!(array.partition_point(|v| *v < 3), 1);
+        assert_eq!(array.partition_point(|v| *v <= 3), 3);
+        // All true.
+        assert_eq!(array.partition_point(|_| true), 4);
+        // All false.
+        assert_eq!(array.partition_point(|_| false), 0);
+
+        let array = LazyArray16::<u16>::new(&[]);
+        assert_eq!(array.partition_point(|_| true), 0);
+    }
+
+    #[test]
+    fn binary_search_by_key() {
+        let data = &[0x00, 0x01, 0x00, 0x03, 0x00, 0x05];
+
+        let array = LazyArray16::<u16>::new(data);
+        assert_eq!(array.binary_search_by_key(&3, |v| *v), Some((1, 3)));
+        assert_eq!(array.binary_search_by_key(&10, |v| *v * 2), Some((2, 5)));
+        assert_eq!(array.binary_search_by_key(&4, |v| *v), None);
+
+        let array = LazyArray32::<u16>::new(data);
+        assert_eq!(array.binary_search_by_key(&5, |v| *v), Some((2, 5)));
+        assert_eq!(array.binary_search_by_key(&0, |v| *v), None);
+    }
+
+    #[test]
+    fn equal_range_by() {
+        let array = LazyArray16::<u16>::new(&[
+            0x00, 0x01, 0x00, 0x03, 0x00, 0x03, 0x00, 0x03, 0x00, 0x05,
+        ]);
+        assert_eq!(array.equal_range_by(|v| v.cmp(&3)), 1..4);
+        assert_eq!(array.equal_range_by(|v| v.cmp(&1)), 0..1);
+        assert_eq!(array.equal_range_by(|v| v.cmp(&5)), 4..5);
+        assert!(array.equal_range_by(|v| v.cmp(&0)).is_empty());
+        assert!(array.equal_range_by(|v| v.cmp(&4)).is_empty());
+        assert!(array.equal_range_by(|v| v.cmp(&6)).is_empty());
+    }
+
+    #[test]
+    fn lazy_array_iter() {
+        let data = &[0x00, 0x01, 0x00, 0x02, 0x00, 0x03];
+        let array = LazyArray16::<u16>::new(data);
+        assert_eq!(array.iter().collect::<std::vec::Vec<_>>(), &[1, 2, 3]);
+        assert_eq!(array.iter().count(), 3);
+
+        let array = LazyArray32::<u16>::new(data);
+        assert_eq!(array.iter().collect::<std::vec::Vec<_>>(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn lazy_array_iter_len() {
+        let data = &[0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0xFF];
+
+        let mut iter = LazyArray16::<u16>::new(data).into_iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        iter.next();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.len(), 0);
+
+        let mut iter = LazyArray32::<u16>::new(data).into_iter();
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.count(), 2);
+    }
+
+    #[test]
+    fn f2dot14_from_f32() {
+        for v in [-2.0, -1.0, -0.5, 0.0, 0.25, 0.5, 1.0].iter() {
+            assert_eq!(F2DOT14::from_f32(*v).to_f32(), *v);
+        }
+
+        assert_eq!(F2DOT14::from_f32(1.0), F2DOT14(16384));
+        assert_eq!(F2DOT14::from_f32(-1.0), F2DOT14(-16384));
+        // Rounded to the nearest value.
+        assert_eq!(F2DOT14::from_f32(0.3), F2DOT14(4915));
+        assert_eq!(F2DOT14::from_f32(-0.3), F2DOT14(-4915));
+        // Clamped.
+        assert_eq!(F2DOT14::from_f32(2.0), F2DOT14(32767));
+        assert_eq!(F2DOT14::from_f32(100.0), F2DOT14(32767));
+        assert_eq!(F2DOT14::from_f32(-2.5), F2DOT14(-32768));
+    }
+
+    #[test]
+    fn num_from_unsigned() {
+        assert_eq!(usize::num_from(0xFFu8), 255);
+        assert_eq!(usize::num_from(0xFFFFu16), 65535);
+        assert_eq!(usize::num_from(0xFFFF_FFFFu32), 4294967295);
+    }
+
+    #[test]
+    fn try_num_from_f64() {
+        assert_eq!(i32::try_num_from(-2147483648.0f64), Some(-2147483648));
+        assert_eq!(i32::try_num_from(-2147483648.9f64), Some(-2147483648));
+        assert_eq!(i32::try_num_from(-2147483649.0f64), None);
+        assert_eq!(i32::try_num_from(2147483647.0f64), Some(2147483647));
+        assert_eq!(i32::try_num_from(2147483647.9f64), Some(2147483647));
+        assert_eq!(i32::try_num_from(2147483648.0f64), None);
+        assert_eq!(i32::try_num_from(f64::from(core::f32::NAN)), None);
+
+        assert_eq!(i16::try_num_from(-32768.0f64), Some(-32768));
+        assert_eq!(i16::try_num_from(-32769.0f64), None);
+        assert_eq!(i16::try_num_from(32767.5f64), Some(32767));
+        assert_eq!(i16::try_num_from(32768.0f64), None);
+
+        assert_eq!(u16::try_num_from(-0.5f64), Some(0));
+        assert_eq!(u16::try_num_from(-1.0f64), None);
+        assert_eq!(u16::try_num_from(65535.0f64), Some(65535));
+        assert_eq!(u16::try_num_from(65536.0f64), None);
+    }
+
+    #[test]
+    fn fixed_raw() {
+        let data = &[0x02, 0xBC, 0x80, 0x00]; // 700.5
+        let a = Fixed::parse(data).unwrap();
+        let b = Fixed::from_raw(0x02BC8000);
+        assert_eq!(a, b);
+        assert_eq!(a.raw(), 0x02BC8000);
+        assert_eq!(a.to_f32(), 700.5);
+        assert_eq!(Fixed::from_raw(-0x10000).to_f32(), -1.0);
+        assert!(Fixed::from_raw(1) > Fixed::from_raw(0));
+    }
+
+    #[test]
+    fn fixed_from_f32() {
+        for v in [-1.0, 0.0, 0.5, 1.0, 700.5, -32768.0].iter() {
+            assert_eq!(Fixed::from_f32(*v).to_f32(), *v);
+        }
+
+        assert_eq!(Fixed::from_f32(1.0).raw(), 0x10000);
+        // Rounded to the nearest value.
+        assert_eq!(Fixed::from_f32(0.3).raw(), 19661);
+        assert_eq!(Fixed::from_f32(-0.3).raw(), -19661);
+        // Clamped.
+        assert_eq!(Fixed::from_f32(40000.0).raw(), 2147483647);
+        assert_eq!(Fixed::from_f32(-40000.0).raw(), -2147483648);
+    }
+
+    #[test]
+    fn lazy_array32_last() {
+        let array = LazyArray32::<u16>::new(&[0x00, 0x01, 0x00, 0x02, 0xFF]);
+        assert!(!array.is_empty());
+        assert_eq!(array.last(), Some(2));
+
+        let array = LazyArray32::<u16>::new(&[0xFF]);
+        assert!(array.is_empty());
+        assert_eq!(array.last(), None);
+    }
+
+    #[test]
+    fn lazy_array_slice() {
+        // Three elements and a trailing byte.
+        let data = &[0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0xFF];
+        let array = LazyArray16::<u16>::new(data);
+        assert_eq!(array.len(), 3);
+
+        let slice = array.slice(1..3).unwrap();
+        assert_eq!(slice.len(), 2);
+        assert_eq!(slice.get(0), Some(2));
+        assert_eq!(slice.get(1), Some(3));
+        assert!(array.slice(3..3).unwrap().is_empty());
+
+        assert!(array.slice(2..4).is_none());
+        assert!(array.slice(4..4).is_none());
+        let (start, end) = (2, 1);
+        assert!(array.slice(start..end).is_none());
+    }
+}
@@ -4,7 +4,7 @@ use core::convert::TryFrom;
 use core::num::NonZeroU16;
 
 use crate::NormalizedCoordinate;
-use crate::parser::{Stream, FromData, LazyArray16};
+use crate::parser::{Stream, FromData, F2DOT14, LazyArray16, LazyArrayIter16};
 
 
 #[derive(Clone, Copy)]
@@ -55,6 +55,61 @@ impl<'a> Table<'a> {
 
         Some(())
     }
+
+    #[inline]
+    pub fn segment_maps(&self) -> SegmentMaps<'a> {
+        SegmentMaps {
+            data: self.data,
+            count: self.axis_count.get(),
+        }
+    }
+}
+
+
+/// An iterator over [axis variations](https://docs.microsoft.com/en-us/typography/opentype/spec/avar)
+/// segment maps.
+///
+/// Yields a segment map per axis, in the `fvar` axes order.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct SegmentMaps<'a> {
+    data: &'a [u8],
+    count: u16,
+}
+
+impl<'a> Iterator for SegmentMaps<'a> {
+    type Item = SegmentMap<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count == 0 {
+            return None;
+        }
+
+        self.count -= 1;
+        let mut s = Stream::new(self.data);
+        let count: u16 = s.read()?;
+        let records = s.read_array16::<AxisValueMapRecord>(count)?;
+        self.data = s.tail()?;
+        Some(SegmentMap { records: records.into_iter() })
+    }
+}
+
+
+/// An iterator over `fromCoordinate` and `toCoordinate` pairs of a single axis segment map.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct SegmentMap<'a> {
+    records: LazyArrayIter16<'a, AxisValueMapRecord>,
+}
+
+impl<'a> Iterator for SegmentMap<'a> {
+    type Item = (F2DOT14, F2DOT14);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.records.next()?;
+        Some((F2DOT14(record.from_coordinate), F2DOT14(record.to_coordinate)))
+    }
 }
 
 fn map_value(map: &LazyArray16<AxisValueMapRecord>, value: i16) -> Option<i16> {
@@ -125,3 +180,45 @@ impl FromData for AxisValueMapRecord {
         })
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_maps() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x00, // reserved
+            0x00, 0x02, // axis count: 2
+            // Segment Map [0]
+            0x00, 0x03, // position map count: 3
+            0xC0, 0x00, // from coordinate [0]: -1
+            0xC0, 0x00, // to coordinate [0]: -1
+            0x00, 0x00, // from coordinate [1]: 0
+            0x00, 0x00, // to coordinate [1]: 0
+            0x40, 0x00, // from coordinate [2]: 1
+            0x40, 0x00, // to coordinate [2]: 1
+            // Segment Map [1]
+            0x00, 0x01, // position map count: 1
+            0x20, 0x00, // from coordinate [0]: 0.5
+            0x10, 0x00, // to coordinate [0]: 0.25
+        ];
+
+        let table = Table::parse(data).unwrap();
+        let mut maps = table.segment_maps();
+
+        let mut map = maps.next().unwrap();
+        assert_eq!(map.next(), Some((F2DOT14(-16384), F2DOT14(-16384))));
+        assert_eq!(map.next(), Some((F2DOT14(0), F2DOT14(0))));
+        assert_eq!(map.next(), Some((F2DOT14(16384), F2DOT14(16384))));
+        assert_eq!(map.next(), None);
+
+        let mut map = maps.next().unwrap();
+        assert_eq!(map.next(), Some((F2DOT14(8192), F2DOT14(4096))));
+        assert_eq!(map.next(), None);
+
+        assert!(maps.next().is_none());
+    }
+}
@@ -351,6 +351,15 @@ pub fn glyph_name<'a>(metadata: &'a Metadata, glyph_id: GlyphId) -> Option<&'a s
     }
 }
 
+/// Returns a raw Type 2 charstring from the CharStrings INDEX.
+///
+/// Subroutines are not resolved, so the charstring can be interpreted
+/// only using the font's global and local subroutines.
+#[inline]
+pub fn glyph_charstring<'a>(metadata: &Metadata<'a>, glyph_id: GlyphId) -> Option<&'a [u8]> {
+    metadata.char_strings.get(u32::from(glyph_id.0))
+}
+
 pub fn outline(
     metadata: &Metadata,
     glyph_id: GlyphId,
@@ -757,33 +766,9 @@ mod tests {
     use super::*;
     use std::vec::Vec;
     use std::string::String;
-    use std::fmt::Write;
-    use crate::writer;
+    use crate::writer::{self, Builder};
     use writer::TtfType::*;
 
-    struct Builder(String);
-    impl OutlineBuilder for Builder {
-        fn move_to(&mut self, x: f32, y: f32) {
-            write!(&mut self.0, "M {} {} ", x, y).unwrap();
-        }
-
-        fn line_to(&mut self, x: f32, y: f32) {
-            write!(&mut self.0, "L {} {} ", x, y).unwrap();
-        }
-
-        fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
-            write!(&mut self.0, "Q {} {} {} {} ", x1, y1, x, y).unwrap();
-        }
-
-        fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
-            write!(&mut self.0, "C {} {} {} {} {} {} ", x1, y1, x2, y2, x, y).unwrap();
-        }
-
-        fn close(&mut self) {
-            write!(&mut self.0, "Z ").unwrap();
-        }
-    }
-
     fn gen_cff(
         global_subrs: &[&[writer::TtfType]],
         local_subrs: &[&[writer::TtfType]],
@@ -972,6 +957,53 @@ mod tests {
         assert_eq!(rect, Rect { x_min: 10, y_min: 0, x_max: 10, y_max: 0 });
     }
 
+    #[test]
+    fn raw_charstrings() {
+        let data = writer::convert(&[
+            // Header
+            UInt8(1), // major version
+            UInt8(0), // minor version
+            UInt8(4), // header size
+            UInt8(0), // absolute offset
+
+            // Name INDEX
+            UInt16(0), // count
+
+            // Top DICT
+            // INDEX
+            UInt16(1), // count
+            UInt8(1), // offset size
+            UInt8(1), // index[0]
+            UInt8(3), // index[1]
+            // Data
+            CFFInt(17),
+            UInt8(top_dict_operator::CHAR_STRINGS_OFFSET as u8),
+
+            // String INDEX
+            UInt16(0), // count
+
+            // Global Subroutines INDEX
+            UInt16(0), // count
+
+            // CharString INDEX
+            UInt16(2), // count
+            UInt8(1), // offset size
+            UInt8(1), // index[0]
+            UInt8(1), // index[1]
+            UInt8(4), // index[2]
+            // Data
+            CFFInt(10),
+            UInt8(operator::HORIZONTAL_MOVE_TO),
+            UInt8(operator::ENDCHAR),
+        ]);
+
+        let metadata = parse_metadata(&data).unwrap();
+        assert_eq!(glyph_charstring(&metadata, GlyphId(0)), Some(&[][..]));
+        assert_eq!(glyph_charstring(&metadata, GlyphId(1)),
+                   Some(&[0x95, operator::HORIZONTAL_MOVE_TO, operator::ENDCHAR][..]));
+        assert_eq!(glyph_charstring(&metadata, GlyphId(2)), None);
+    }
+
     fn rect(x_min: i16, y_min: i16, x_max: i16, y_max: i16) -> Rect {
         Rect { x_min, y_min, x_max, y_max }
     }
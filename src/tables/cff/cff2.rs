@@ -144,14 +144,31 @@ pub(crate) fn parse_metadata(data: &[u8]) -> Option<Metadata> {
 }
 
 
+/// Returns a raw Type 2 charstring from the CharStrings INDEX.
+///
+/// Subroutines are not resolved and blend operators are not applied.
+#[inline]
+pub(crate) fn glyph_charstring<'a>(metadata: &Metadata<'a>, glyph_id: GlyphId) -> Option<&'a [u8]> {
+    metadata.char_strings.get(u32::from(glyph_id.0))
+}
+
+#[inline]
+pub(crate) fn region_scalars(
+    metadata: &Metadata,
+    coordinates: &[NormalizedCoordinate],
+) -> RegionScalars {
+    RegionScalars::new(&metadata.item_variation_store.regions, coordinates)
+}
+
 pub(crate) fn outline(
     metadata: &Metadata,
     coordinates: &[NormalizedCoordinate],
+    region_scalars: Option<&RegionScalars>,
     glyph_id: GlyphId,
     builder: &mut dyn OutlineBuilder,
 ) -> Option<Rect> {
     let data = metadata.char_strings.get(u32::from(glyph_id.0))?;
-    parse_char_string(data, metadata, coordinates, builder).ok()
+    parse_char_string(data, metadata, coordinates, region_scalars, builder).ok()
 }
 
 #[derive(Clone, Copy, Default)]
@@ -278,6 +295,7 @@ impl Scalars {
 struct CharStringParserContext<'a> {
     metadata: &'a Metadata<'a>,
     coordinates: &'a [NormalizedCoordinate],
+    region_scalars: Option<&'a RegionScalars>,
     scalars: Scalars,
     had_vsindex: bool,
     had_blend: bool,
@@ -290,9 +308,12 @@ impl CharStringParserContext<'_> {
 
         let indices = self.metadata.item_variation_store.region_indices(index)
             .ok_or(CFFError::InvalidItemVariationDataIndex)?;
+        let regions = self.metadata.item_variation_store.regions;
         for index in indices {
-            let scalar = self.metadata.item_variation_store.regions
-                .evaluate_region(index, self.coordinates);
+            let scalar = match self.region_scalars {
+                Some(scalars) => regions.evaluate_region_cached(index, self.coordinates, scalars),
+                None => regions.evaluate_region(index, self.coordinates),
+            };
             self.scalars.push(scalar)
                 .ok_or(CFFError::BlendRegionsLimitReached)?;
         }
@@ -305,11 +326,13 @@ fn parse_char_string(
     data: &[u8],
     metadata: &Metadata,
     coordinates: &[NormalizedCoordinate],
+    region_scalars: Option<&RegionScalars>,
     builder: &mut dyn OutlineBuilder,
 ) -> Result<Rect, CFFError> {
     let mut ctx = CharStringParserContext {
         metadata,
         coordinates,
+        region_scalars,
         scalars: Scalars::default(),
         had_vsindex: false,
         had_blend: false,
@@ -594,7 +594,7 @@ impl CharStringParser<'_> {
     #[inline]
     pub fn parse_fixed(&mut self, s: &mut Stream) -> Result<(), CFFError> {
         let n = s.read::<Fixed>().ok_or(CFFError::ReadOutOfBounds)?;
-        self.stack.push(n.0)?;
+        self.stack.push(n.to_f32())?;
         Ok(())
     }
 }
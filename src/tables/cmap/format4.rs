@@ -39,7 +39,10 @@ pub fn parse(data: &[u8], code_point: u32) -> Option<u16> {
                 let id_range_offset = id_range_offsets.get(index)?;
                 let id_delta = id_deltas.get(index)?;
                 if id_range_offset == 0 {
-                    return Some(code_point.wrapping_add(id_delta as u16));
+                    // The last segment must map 0xFFFF to `.notdef` using `idDelta` of 1,
+                    // so the resulting 0 glyph must not be treated as a real glyph.
+                    let glyph_id = code_point.wrapping_add(id_delta as u16);
+                    return if glyph_id != 0 { Some(glyph_id) } else { None };
                 }
 
                 let delta = (u32::from(code_point) - u32::from(start_value)) * 2;
@@ -123,6 +126,37 @@ mod tests {
         assert_eq!(parse(data, 0x42), None);
     }
 
+    #[test]
+    fn sentinel_segment() {
+        let data = &[
+            0x00, 0x04, // format: 4
+            0x00, 0x20, // subtable size: 32
+            0x00, 0x00, // language ID: 0
+            0x00, 0x04, // 2 x segCount: 4
+            0x00, 0x02, // search range: 2
+            0x00, 0x00, // entry selector: 0
+            0x00, 0x02, // range shift: 2
+            // End character codes
+            0xFF, 0xFE, // char code [0]: 65534
+            0xFF, 0xFF, // char code [1]: 65535
+            0x00, 0x00, // reserved: 0
+            // Start character codes
+            0xFF, 0xFE, // char code [0]: 65534
+            0xFF, 0xFF, // char code [1]: 65535
+            // Deltas
+            0x00, 0x03, // delta [0]: 3
+            0x00, 0x01, // delta [1]: 1
+            // Offsets into Glyph index array
+            0x00, 0x00, // offset [0]: 0
+            0x00, 0x00, // offset [1]: 0
+        ];
+
+        // U+FFFE is mapped to glyph 1, because of the modulo arithmetic.
+        assert_eq!(parse(data, 0xFFFE), Some(1));
+        // The sentinel segment maps U+FFFF to `.notdef`.
+        assert_eq!(parse(data, 0xFFFF), None);
+    }
+
     #[test]
     fn continuous_range() {
         let data = &[
@@ -23,6 +23,7 @@ mod format10;
 mod format12;
 mod format13;
 mod format14;
+mod mac_roman;
 
 pub use format14::GlyphVariationResult;
 
@@ -95,6 +96,34 @@ impl<'a> Subtable<'a> {
         self.format
     }
 
+    /// Returns subtable's raw data.
+    ///
+    /// The data starts with the format field and is bounded by the subtable's `length`.
+    /// When `length` is malformed, the data will span to the end of the `cmap` table.
+    #[inline]
+    pub fn data(&self) -> &'a [u8] {
+        let length = match self.format {
+            Format::ByteEncodingTable |
+            Format::HighByteMappingThroughTable |
+            Format::SegmentMappingToDeltaValues |
+            Format::TrimmedTableMapping => {
+                Stream::read_at::<u16>(self.subtable_data, 2).map(usize::from)
+            }
+            Format::MixedCoverage |
+            Format::TrimmedArray |
+            Format::SegmentedCoverage |
+            Format::ManyToOneRangeMappings => {
+                // Has a reserved u16 after the format.
+                Stream::read_at::<u32>(self.subtable_data, 4).map(usize::num_from)
+            }
+            Format::UnicodeVariationSequences => {
+                Stream::read_at::<u32>(self.subtable_data, 2).map(usize::num_from)
+            }
+        };
+
+        length.and_then(|len| self.subtable_data.get(0..len)).unwrap_or(self.subtable_data)
+    }
+
     /// Checks that the current encoding is Unicode compatible.
     #[inline]
     pub fn is_unicode(&self) -> bool {
@@ -116,6 +145,25 @@ impl<'a> Subtable<'a> {
         }
     }
 
+    /// Checks that the current encoding is Mac Roman.
+    #[inline]
+    pub fn is_mac_roman(&self) -> bool {
+        // https://docs.microsoft.com/en-us/typography/opentype/spec/name#macintosh-encoding-ids-script-manager-codes
+        const MACINTOSH_ROMAN_ENCODING_ID: u16 = 0;
+
+        self.platform_id == PlatformId::Macintosh && self.encoding_id == MACINTOSH_ROMAN_ENCODING_ID
+    }
+
+    /// Maps a character to a glyph ID using the Mac Roman encoding.
+    ///
+    /// Like `glyph_index`, it doesn't check that the current encoding is Mac Roman.
+    ///
+    /// Returns `None` when the character cannot be represented in Mac Roman.
+    #[inline]
+    pub fn glyph_index_mac_roman(&self, c: char) -> Option<GlyphId> {
+        self.glyph_index(u32::from(mac_roman::encode(c)?))
+    }
+
     /// Maps a character to a glyph ID.
     ///
     /// This is a low-level method and unlike `Face::glyph_index` it doesn't
@@ -160,7 +208,7 @@ impl<'a> Subtable<'a> {
             }
         };
 
-        glyph.map(GlyphId)
+        glyph.filter(|id| *id != 0).map(GlyphId)
     }
 
     /// Resolves a variation of a glyph ID from two code points.
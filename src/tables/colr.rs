@@ -0,0 +1,173 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/colr
+
+use core::cmp::Ordering;
+
+use crate::{GlyphId, Rect};
+use crate::parser::{Stream, FromData, LazyArray32, Offset24, Offset32, Offset};
+
+
+#[derive(Clone, Copy)]
+struct Clip {
+    start_glyph_id: GlyphId,
+    end_glyph_id: GlyphId,
+    clip_box_offset: Offset24,
+}
+
+impl FromData for Clip {
+    const SIZE: usize = 7;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(Clip {
+            start_glyph_id: s.read::<GlyphId>()?,
+            end_glyph_id: s.read::<GlyphId>()?,
+            clip_box_offset: s.read::<Offset24>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy)]
+pub(crate) struct Table<'a> {
+    // Clip box offsets are from the start of the ClipList.
+    clip_list: &'a [u8],
+    clips: LazyArray32<'a, Clip>,
+}
+
+impl<'a> Table<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u16 = s.read()?;
+        if version > 1 {
+            return None;
+        }
+
+        let mut table = Table {
+            clip_list: &[],
+            clips: LazyArray32::default(),
+        };
+
+        // Only version 1 has a ClipList.
+        if version == 0 {
+            return Some(table);
+        }
+
+        s.skip::<u16>(); // numBaseGlyphRecords
+        s.skip::<Offset32>(); // baseGlyphRecordsOffset
+        s.skip::<Offset32>(); // layerRecordsOffset
+        s.skip::<u16>(); // numLayerRecords
+        s.skip::<Offset32>(); // baseGlyphListOffset
+        s.skip::<Offset32>(); // layerListOffset
+        let clip_list_offset: Option<Offset32> = s.read()?;
+
+        if let Some(offset) = clip_list_offset {
+            let clip_list = data.get(offset.to_usize()..)?;
+            let mut s = Stream::new(clip_list);
+            let format: u8 = s.read()?;
+            if format == 1 {
+                let count: u32 = s.read()?;
+                table.clips = s.read_array32::<Clip>(count)?;
+                table.clip_list = clip_list;
+            }
+        }
+
+        Some(table)
+    }
+
+    pub fn clip_box(&self, glyph_id: GlyphId) -> Option<Rect> {
+        // Clips are sorted by glyph ID and do not overlap.
+        let (_, clip) = self.clips.binary_search_by(|clip| {
+            if clip.end_glyph_id < glyph_id {
+                Ordering::Less
+            } else if clip.start_glyph_id > glyph_id {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })?;
+
+        let mut s = Stream::new_at(self.clip_list, clip.clip_box_offset.to_usize())?;
+        let format: u8 = s.read()?;
+        // Format 2 has an additional variation index, which we ignore,
+        // so a variable clip box is returned for the default instance.
+        if format != 1 && format != 2 {
+            return None;
+        }
+
+        Some(Rect {
+            x_min: s.read::<i16>()?,
+            y_min: s.read::<i16>()?,
+            x_max: s.read::<i16>()?,
+            y_max: s.read::<i16>()?,
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLR_DATA: &[u8] = &[
+        0x00, 0x01, // version: 1
+        0x00, 0x00, // number of base glyph records: 0
+        0x00, 0x00, 0x00, 0x00, // base glyph records offset: NULL
+        0x00, 0x00, 0x00, 0x00, // layer records offset: NULL
+        0x00, 0x00, // number of layer records: 0
+        0x00, 0x00, 0x00, 0x00, // base glyph list offset: NULL
+        0x00, 0x00, 0x00, 0x00, // layer list offset: NULL
+        0x00, 0x00, 0x00, 0x22, // clip list offset: 34
+        0x00, 0x00, 0x00, 0x00, // var index map offset: NULL
+        0x00, 0x00, 0x00, 0x00, // item variation store offset: NULL
+        // Clip List
+        0x01, // format: 1
+        0x00, 0x00, 0x00, 0x02, // count: 2
+        0x00, 0x02, // start glyph ID [0]: 2
+        0x00, 0x04, // end glyph ID [0]: 4
+        0x00, 0x00, 0x13, // clip box offset [0]: 19
+        0x00, 0x0A, // start glyph ID [1]: 10
+        0x00, 0x0A, // end glyph ID [1]: 10
+        0x00, 0x00, 0x1C, // clip box offset [1]: 28
+        // Clip Box [0]
+        0x01, // format: 1
+        0x00, 0x00, // x min: 0
+        0xFF, 0x38, // y min: -200
+        0x03, 0xE8, // x max: 1000
+        0x03, 0x20, // y max: 800
+        // Clip Box [1]
+        0x02, // format: 2
+        0x00, 0x0A, // x min: 10
+        0x00, 0x14, // y min: 20
+        0x00, 0x1E, // x max: 30
+        0x00, 0x28, // y max: 40
+        0x00, 0x00, 0x00, 0x00, // var index base: 0
+    ];
+
+    #[test]
+    fn clip_box() {
+        let table = Table::parse(COLR_DATA).unwrap();
+        let rect = Rect { x_min: 0, y_min: -200, x_max: 1000, y_max: 800 };
+        assert_eq!(table.clip_box(GlyphId(2)), Some(rect));
+        assert_eq!(table.clip_box(GlyphId(4)), Some(rect));
+        assert_eq!(table.clip_box(GlyphId(10)),
+                   Some(Rect { x_min: 10, y_min: 20, x_max: 30, y_max: 40 }));
+        assert_eq!(table.clip_box(GlyphId(1)), None);
+        assert_eq!(table.clip_box(GlyphId(5)), None);
+        assert_eq!(table.clip_box(GlyphId(11)), None);
+    }
+
+    #[test]
+    fn version_0() {
+        let data = &[
+            0x00, 0x00, // version: 0
+            0x00, 0x00, // number of base glyph records: 0
+            0x00, 0x00, 0x00, 0x00, // base glyph records offset: NULL
+            0x00, 0x00, 0x00, 0x00, // layer records offset: NULL
+            0x00, 0x00, // number of layer records: 0
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.clip_box(GlyphId(0)), None);
+    }
+}
@@ -0,0 +1,139 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/cpal
+
+use crate::parser::{Stream, FromData, LazyArray16, Offset32, Offset};
+
+
+/// A color in RGBA format.
+///
+/// `CPAL` stores colors in BGRA order, so they are reordered during parsing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RgbaColor {
+    /// Red component.
+    pub red: u8,
+    /// Green component.
+    pub green: u8,
+    /// Blue component.
+    pub blue: u8,
+    /// Alpha component.
+    pub alpha: u8,
+}
+
+impl FromData for RgbaColor {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let blue: u8 = s.read()?;
+        let green: u8 = s.read()?;
+        let red: u8 = s.read()?;
+        let alpha: u8 = s.read()?;
+        Some(RgbaColor { red, green, blue, alpha })
+    }
+}
+
+
+#[derive(Clone, Copy)]
+pub(crate) struct Table<'a> {
+    palette_entries: u16,
+    color_record_indices: LazyArray16<'a, u16>,
+    colors: LazyArray16<'a, RgbaColor>,
+}
+
+impl<'a> Table<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u16 = s.read()?;
+        if version > 1 {
+            return None;
+        }
+
+        let palette_entries: u16 = s.read()?;
+        let palettes_count: u16 = s.read()?;
+        let colors_count: u16 = s.read()?;
+        let colors_offset: Offset32 = s.read()?;
+        let color_record_indices = s.read_array16::<u16>(palettes_count)?;
+        // Version 1 has additional palette types, labels and entry labels, which we ignore.
+
+        let colors = Stream::read_array16_at::<RgbaColor>(
+            data, colors_offset.to_usize(), colors_count,
+        )?;
+
+        Some(Table {
+            palette_entries,
+            color_record_indices,
+            colors,
+        })
+    }
+
+    #[inline]
+    pub fn palettes_count(&self) -> u16 {
+        self.color_record_indices.len()
+    }
+
+    /// Returns all colors of a palette.
+    ///
+    /// Each palette has the same number of colors.
+    #[inline]
+    pub fn palette(&self, index: u16) -> Option<LazyArray16<'a, RgbaColor>> {
+        let start = self.color_record_indices.get(index)?;
+        self.colors.subarray(start, self.palette_entries)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palettes() {
+        let data = &[
+            0x00, 0x00, // version: 0
+            0x00, 0x02, // number of palette entries: 2
+            0x00, 0x02, // number of palettes: 2
+            0x00, 0x03, // number of color records: 3
+            0x00, 0x00, 0x00, 0x10, // color records offset: 16
+            0x00, 0x00, // color record index [0]: 0
+            0x00, 0x01, // color record index [1]: 1
+            // Color Records
+            0x00, 0x00, 0xFF, 0xFF, // color [0]: red
+            0x00, 0xFF, 0x00, 0x80, // color [1]: green, half transparent
+            0xFF, 0x00, 0x00, 0xFF, // color [2]: blue
+        ];
+
+        let red = RgbaColor { red: 255, green: 0, blue: 0, alpha: 255 };
+        let green = RgbaColor { red: 0, green: 255, blue: 0, alpha: 128 };
+        let blue = RgbaColor { red: 0, green: 0, blue: 255, alpha: 255 };
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.palettes_count(), 2);
+
+        let palette = table.palette(0).unwrap();
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette.iter().collect::<std::vec::Vec<_>>(), vec![red, green]);
+
+        // Palettes can share colors.
+        let palette = table.palette(1).unwrap();
+        assert_eq!(palette.iter().collect::<std::vec::Vec<_>>(), vec![green, blue]);
+
+        assert!(table.palette(2).is_none());
+    }
+
+    #[test]
+    fn palette_out_of_bounds() {
+        let data = &[
+            0x00, 0x00, // version: 0
+            0x00, 0x02, // number of palette entries: 2
+            0x00, 0x01, // number of palettes: 1
+            0x00, 0x01, // number of color records: 1
+            0x00, 0x00, 0x00, 0x0E, // color records offset: 14
+            0x00, 0x00, // color record index [0]: 0
+            // Color Records
+            0x00, 0x00, 0xFF, 0xFF, // color [0]: red
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert!(table.palette(0).is_none());
+    }
+}
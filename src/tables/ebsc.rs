@@ -0,0 +1,154 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/ebsc
+
+use crate::parser::{Stream, FromData, LazyArray32};
+
+
+/// An [embedded bitmap scaling](https://docs.microsoft.com/en-us/typography/opentype/spec/ebsc)
+/// entry.
+///
+/// Describes how an existing bitmap strike should be scaled to a size
+/// that doesn't have its own strike.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BitmapScale {
+    /// Horizontal pixels per em of the strike that should be scaled.
+    pub source_ppem_x: u8,
+
+    /// Vertical pixels per em of the strike that should be scaled.
+    pub source_ppem_y: u8,
+
+    /// Horizontal scale factor that should be applied to the strike's bitmaps.
+    pub scale_x: f32,
+
+    /// Vertical scale factor that should be applied to the strike's bitmaps.
+    pub scale_y: f32,
+}
+
+
+#[derive(Clone, Copy)]
+struct BitmapScaleRecord {
+    ppem_x: u8,
+    ppem_y: u8,
+    substitute_ppem_x: u8,
+    substitute_ppem_y: u8,
+}
+
+impl FromData for BitmapScaleRecord {
+    const SIZE: usize = 28;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        s.advance(24); // hori + vert SbitLineMetrics
+        Some(BitmapScaleRecord {
+            ppem_x: s.read::<u8>()?,
+            ppem_y: s.read::<u8>()?,
+            substitute_ppem_x: s.read::<u8>()?,
+            substitute_ppem_y: s.read::<u8>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy)]
+pub(crate) struct Table<'a> {
+    records: LazyArray32<'a, BitmapScaleRecord>,
+}
+
+impl<'a> Table<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+
+        let major_version: u16 = s.read()?;
+        let minor_version: u16 = s.read()?;
+        if !(major_version == 2 && minor_version == 0) {
+            return None;
+        }
+
+        let count: u32 = s.read()?;
+        let records = s.read_array32::<BitmapScaleRecord>(count)?;
+
+        Some(Table { records })
+    }
+
+    pub fn scale(&self, pixels_per_em: u16) -> Option<BitmapScale> {
+        let record = self.records.into_iter()
+            .find(|r| u16::from(r.ppem_x) == pixels_per_em)?;
+
+        if record.substitute_ppem_x == 0 || record.substitute_ppem_y == 0 {
+            return None;
+        }
+
+        Some(BitmapScale {
+            source_ppem_x: record.substitute_ppem_x,
+            source_ppem_y: record.substitute_ppem_y,
+            scale_x: f32::from(record.ppem_x) / f32::from(record.substitute_ppem_x),
+            scale_y: f32::from(record.ppem_y) / f32::from(record.substitute_ppem_y),
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE_METRICS: &[u8] = &[0; 12];
+
+    fn table_data(records: &[[u8; 4]]) -> std::vec::Vec<u8> {
+        let mut data = vec![
+            0x00, 0x02, // major version: 2
+            0x00, 0x00, // minor version: 0
+            0x00, 0x00, 0x00, records.len() as u8, // number of sizes
+        ];
+
+        for record in records {
+            data.extend_from_slice(LINE_METRICS); // hori
+            data.extend_from_slice(LINE_METRICS); // vert
+            data.extend_from_slice(record);
+        }
+
+        data
+    }
+
+    #[test]
+    fn scale() {
+        let data = table_data(&[
+            [24, 24, 12, 12], // 24 ppem from 12 ppem
+            [18, 9, 12, 12], // 18x9 ppem from 12 ppem
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.scale(24), Some(BitmapScale {
+            source_ppem_x: 12,
+            source_ppem_y: 12,
+            scale_x: 2.0,
+            scale_y: 2.0,
+        }));
+        assert_eq!(table.scale(18), Some(BitmapScale {
+            source_ppem_x: 12,
+            source_ppem_y: 12,
+            scale_x: 1.5,
+            scale_y: 0.75,
+        }));
+        assert_eq!(table.scale(12), None);
+    }
+
+    #[test]
+    fn zero_substitute() {
+        let data = table_data(&[[24, 24, 0, 0]]);
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.scale(24), None);
+    }
+
+    #[test]
+    fn invalid_version() {
+        let data = &[
+            0x00, 0x01, // major version: 1
+            0x00, 0x00, // minor version: 0
+            0x00, 0x00, 0x00, 0x00, // number of sizes: 0
+        ];
+
+        assert!(Table::parse(data).is_none());
+    }
+}
@@ -3,6 +3,7 @@
 use core::num::NonZeroU16;
 
 use crate::{Tag, NormalizedCoordinate};
+use crate::stat;
 use crate::parser::{Stream, FromData, Fixed, Offset16, Offset, LazyArray16, LazyArrayIter16, f32_bound};
 
 
@@ -40,9 +41,54 @@ impl VariationAxis {
 }
 
 
+/// A [named instance](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar#instancerecord).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct NamedInstance<'a> {
+    /// An instance subfamily name in the `name` table.
+    pub subfamily_name_id: u16,
+    /// An instance PostScript name in the `name` table.
+    pub post_script_name_id: Option<u16>,
+    coordinates: LazyArray16<'a, Fixed>,
+}
+
+impl<'a> NamedInstance<'a> {
+    /// Returns instance's coordinates in user space. One per variation axis.
+    #[inline]
+    pub fn coordinates(&self) -> NamedInstanceCoordinates<'a> {
+        NamedInstanceCoordinates { iter: self.coordinates.into_iter() }
+    }
+}
+
+
+/// An iterator over named instance coordinates.
+#[allow(missing_debug_implementations)]
+#[derive(Clone, Copy, Default)]
+pub struct NamedInstanceCoordinates<'a> {
+    iter: LazyArrayIter16<'a, Fixed>,
+}
+
+impl<'a> Iterator for NamedInstanceCoordinates<'a> {
+    type Item = f32;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|v| v.to_f32())
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+}
+
+
 #[derive(Clone, Copy)]
 pub(crate) struct Table<'a> {
     axes: LazyArray16<'a, VariationAxisRecord>,
+    instances: &'a [u8],
+    instance_count: u16,
+    instance_size: u16,
 }
 
 impl<'a> Table<'a> {
@@ -56,6 +102,9 @@ impl<'a> Table<'a> {
         let axes_array_offset: Offset16 = s.read()?;
         s.skip::<u16>(); // reserved
         let axis_count: u16 = s.read()?;
+        let axis_size: u16 = s.read()?;
+        let mut instance_count: u16 = s.read()?;
+        let instance_size: u16 = s.read()?;
 
         // 'If axisCount is zero, then the font is not functional as a variable font,
         // and must be treated as a non-variable font;
@@ -65,13 +114,63 @@ impl<'a> Table<'a> {
         let mut s = Stream::new_at(data, axes_array_offset.to_usize())?;
         let axes = s.read_array16::<VariationAxisRecord>(axis_count.get())?;
 
-        Some(Table { axes })
+        // Instances are stored right after the axes.
+        let instances_offset = axes_array_offset.to_usize()
+            + usize::from(axis_count.get()) * usize::from(axis_size);
+        let instances = data.get(instances_offset..).unwrap_or(&[]);
+
+        // An instance consists of subfamilyNameID, flags and coordinates.
+        if usize::from(instance_size) < usize::from(axis_count.get()) * Fixed::SIZE + 4 {
+            instance_count = 0;
+        } else {
+            // Ignore instances that are past the end of the table,
+            // so the count matches the number of instances we can actually parse.
+            let available = instances.len() / usize::from(instance_size);
+            if available < usize::from(instance_count) {
+                instance_count = available as u16;
+            }
+        }
+
+        Some(Table { axes, instances, instance_count, instance_size })
     }
 
     pub fn axes(&self) -> VariationAxes<'a> {
         VariationAxes { iter: self.axes.into_iter() }
     }
 
+    #[inline]
+    pub fn axis_count(&self) -> u16 {
+        self.axes.len()
+    }
+
+    #[inline]
+    pub fn named_instances_count(&self) -> u16 {
+        self.instance_count
+    }
+
+    pub fn named_instance(&self, index: u16) -> Option<NamedInstance<'a>> {
+        if index >= self.instance_count {
+            return None;
+        }
+
+        let offset = usize::from(index) * usize::from(self.instance_size);
+        let data = self.instances.get(offset..offset + usize::from(self.instance_size))?;
+        let mut s = Stream::new(data);
+        let subfamily_name_id: u16 = s.read()?;
+        s.skip::<u16>(); // flags
+        let coordinates = s.read_array16::<Fixed>(self.axes.len())?;
+
+        // `postScriptNameID` is optional and can be detected only via `instanceSize`.
+        // 0xFFFF indicates that there is no name.
+        let post_script_name_id = s.read::<u16>().filter(|id| *id != 0xFFFF);
+
+        Some(NamedInstance { subfamily_name_id, post_script_name_id, coordinates })
+    }
+
+    pub fn named_instances(&self) -> NamedInstances<'a> {
+        NamedInstances { table: Some(*self), index: 0 }
+    }
+
     // TODO: add axis_by_tag
 }
 
@@ -111,6 +210,69 @@ impl<'a> Iterator for VariationAxes<'a> {
 }
 
 
+/// An iterator over variation axes ordered by the `STAT` table.
+///
+/// Axes are ordered by the `axisOrdering` field of the `STAT` design axis records.
+/// Axes with equal ordering and axes that are not present in `STAT` keep the `fvar` order,
+/// with the latter placed at the end.
+#[allow(missing_debug_implementations)]
+#[derive(Clone, Copy, Default)]
+pub struct StatOrderedVariationAxes<'a> {
+    axes: VariationAxes<'a>,
+    stat: Option<stat::Table<'a>>,
+    // An ordering and an `fvar` index of the previously returned axis.
+    last: Option<(u16, usize)>,
+}
+
+impl<'a> StatOrderedVariationAxes<'a> {
+    pub(crate) fn new(axes: VariationAxes<'a>, stat: Option<stat::Table<'a>>) -> Self {
+        StatOrderedVariationAxes { axes, stat, last: None }
+    }
+}
+
+impl<'a> Iterator for StatOrderedVariationAxes<'a> {
+    type Item = VariationAxis;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // There are only a few axes, so a quadratic search is fine
+        // and doesn't require an allocation.
+        let stat = self.stat;
+        let last = self.last;
+        let (key, axis) = self.axes.enumerate()
+            .map(|(index, axis)| {
+                let ordering = stat.and_then(|stat| stat.axis_ordering(axis.tag));
+                ((ordering.unwrap_or(0xFFFF), index), axis)
+            })
+            .filter(|(key, _)| last.map(|last| *key > last).unwrap_or(true))
+            .min_by_key(|(key, _)| *key)?;
+
+        self.last = Some(key);
+        Some(axis)
+    }
+}
+
+
+/// An iterator over named instances.
+#[allow(missing_debug_implementations)]
+#[derive(Clone, Copy, Default)]
+pub struct NamedInstances<'a> {
+    table: Option<Table<'a>>,
+    index: u16,
+}
+
+impl<'a> Iterator for NamedInstances<'a> {
+    type Item = NamedInstance<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = self.table?;
+        let instance = table.named_instance(self.index)?;
+        self.index += 1;
+        Some(instance)
+    }
+}
+
+
 #[derive(Clone, Copy)]
 struct VariationAxisRecord {
     axis_tag: Tag,
@@ -129,11 +291,211 @@ impl FromData for VariationAxisRecord {
         let mut s = Stream::new(data);
         Some(VariationAxisRecord {
             axis_tag: s.read::<Tag>()?,
-            min_value: s.read::<Fixed>()?.0,
-            def_value: s.read::<Fixed>()?.0,
-            max_value: s.read::<Fixed>()?.0,
+            min_value: s.read::<Fixed>()?.to_f32(),
+            def_value: s.read::<Fixed>()?.to_f32(),
+            max_value: s.read::<Fixed>()?.to_f32(),
             flags: s.read::<u16>()?,
             axis_name_id: s.read::<u16>()?,
         })
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_instances() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x10, // axes array offset: 16
+            0x00, 0x02, // reserved
+            0x00, 0x01, // axis count: 1
+            0x00, 0x14, // axis size: 20
+            0x00, 0x02, // instance count: 2
+            0x00, 0x0A, // instance size: 10
+            // Axis
+            0x77, 0x67, 0x68, 0x74, // axis tag: wght
+            0x00, 0x64, 0x00, 0x00, // min value: 100
+            0x01, 0x90, 0x00, 0x00, // default value: 400
+            0x03, 0x84, 0x00, 0x00, // max value: 900
+            0x00, 0x00, // flags
+            0x01, 0x00, // axis name ID: 256
+            // Instance [0]
+            0x01, 0x01, // subfamily name ID: 257
+            0x00, 0x00, // flags
+            0x01, 0x90, 0x00, 0x00, // coordinate [0]: 400
+            0x01, 0x02, // PostScript name ID: 258
+            // Instance [1]
+            0x01, 0x03, // subfamily name ID: 259
+            0x00, 0x00, // flags
+            0x02, 0xBC, 0x00, 0x00, // coordinate [0]: 700
+            0xFF, 0xFF, // PostScript name ID: none
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.named_instances_count(), 2);
+        assert_eq!(table.named_instances().count(), 2);
+
+        let instance = table.named_instance(0).unwrap();
+        assert_eq!(instance.subfamily_name_id, 257);
+        assert_eq!(instance.post_script_name_id, Some(258));
+        assert_eq!(instance.coordinates().collect::<std::vec::Vec<_>>(), &[400.0]);
+
+        let instance = table.named_instance(1).unwrap();
+        assert_eq!(instance.subfamily_name_id, 259);
+        assert_eq!(instance.post_script_name_id, None);
+        assert_eq!(instance.coordinates().collect::<std::vec::Vec<_>>(), &[700.0]);
+
+        assert!(table.named_instance(2).is_none());
+    }
+
+    #[test]
+    fn padded_named_instances() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x10, // axes array offset: 16
+            0x00, 0x02, // reserved
+            0x00, 0x01, // axis count: 1
+            0x00, 0x14, // axis size: 20
+            0x00, 0x03, // instance count: 3
+            0x00, 0x0E, // instance size: 14
+            // Axis
+            0x77, 0x67, 0x68, 0x74, // axis tag: wght
+            0x00, 0x64, 0x00, 0x00, // min value: 100
+            0x01, 0x90, 0x00, 0x00, // default value: 400
+            0x03, 0x84, 0x00, 0x00, // max value: 900
+            0x00, 0x00, // flags
+            0x01, 0x00, // axis name ID: 256
+            // Instance [0]
+            0x01, 0x01, // subfamily name ID: 257
+            0x00, 0x00, // flags
+            0x00, 0x64, 0x00, 0x00, // coordinate [0]: 100
+            0x01, 0x02, // PostScript name ID: 258
+            0xAA, 0xAA, 0xAA, 0xAA, // padding
+            // Instance [1]
+            0x01, 0x03, // subfamily name ID: 259
+            0x00, 0x00, // flags
+            0x01, 0x90, 0x00, 0x00, // coordinate [0]: 400
+            0x01, 0x04, // PostScript name ID: 260
+            0xAA, 0xAA, 0xAA, 0xAA, // padding
+            // Instance [2]
+            0x01, 0x05, // subfamily name ID: 261
+            0x00, 0x00, // flags
+            0x03, 0x84, 0x00, 0x00, // coordinate [0]: 900
+            0xFF, 0xFF, // PostScript name ID: none
+            0xAA, 0xAA, 0xAA, 0xAA, // padding
+        ];
+
+        let table = Table::parse(data).unwrap();
+        let instances: std::vec::Vec<_> = table.named_instances().map(|instance| {
+            let coordinates: std::vec::Vec<_> = instance.coordinates().collect();
+            (instance.subfamily_name_id, instance.post_script_name_id, coordinates)
+        }).collect();
+
+        assert_eq!(instances, vec![
+            (257, Some(258), vec![100.0]),
+            (259, Some(260), vec![400.0]),
+            (261, None, vec![900.0]),
+        ]);
+    }
+
+    #[test]
+    fn truncated_named_instances() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x10, // axes array offset: 16
+            0x00, 0x02, // reserved
+            0x00, 0x01, // axis count: 1
+            0x00, 0x14, // axis size: 20
+            0x00, 0x03, // instance count: 3
+            0x00, 0x0A, // instance size: 10
+            // Axis
+            0x77, 0x67, 0x68, 0x74, // axis tag: wght
+            0x00, 0x64, 0x00, 0x00, // min value: 100
+            0x01, 0x90, 0x00, 0x00, // default value: 400
+            0x03, 0x84, 0x00, 0x00, // max value: 900
+            0x00, 0x00, // flags
+            0x01, 0x00, // axis name ID: 256
+            // Instance [0]
+            0x01, 0x01, // subfamily name ID: 257
+            0x00, 0x00, // flags
+            0x01, 0x90, 0x00, 0x00, // coordinate [0]: 400
+            0x01, 0x02, // PostScript name ID: 258
+            // Instance [1], truncated
+            0x01, 0x03, // subfamily name ID: 259
+            0x00, 0x00, // flags
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.named_instances_count(), 1);
+        assert_eq!(table.named_instances().count(), 1);
+        assert!(table.named_instance(1).is_none());
+    }
+
+    const TWO_AXES_DATA: &[u8] = &[
+        0x00, 0x01, 0x00, 0x00, // version: 1.0
+        0x00, 0x10, // axes array offset: 16
+        0x00, 0x02, // reserved
+        0x00, 0x02, // axis count: 2
+        0x00, 0x14, // axis size: 20
+        0x00, 0x00, // instance count: 0
+        0x00, 0x0C, // instance size: 12
+        // Axis [0]
+        0x77, 0x67, 0x68, 0x74, // axis tag: wght
+        0x00, 0x64, 0x00, 0x00, // min value: 100
+        0x01, 0x90, 0x00, 0x00, // default value: 400
+        0x03, 0x84, 0x00, 0x00, // max value: 900
+        0x00, 0x00, // flags
+        0x01, 0x00, // axis name ID: 256
+        // Axis [1]
+        0x77, 0x64, 0x74, 0x68, // axis tag: wdth
+        0x00, 0x32, 0x00, 0x00, // min value: 50
+        0x00, 0x64, 0x00, 0x00, // default value: 100
+        0x00, 0xC8, 0x00, 0x00, // max value: 200
+        0x00, 0x00, // flags
+        0x01, 0x01, // axis name ID: 257
+    ];
+
+    #[test]
+    fn axes_order() {
+        let table = Table::parse(TWO_AXES_DATA).unwrap();
+        let tags: std::vec::Vec<_> = table.axes().map(|axis| axis.tag).collect();
+        assert_eq!(tags, vec![Tag::from_bytes(b"wght"), Tag::from_bytes(b"wdth")]);
+
+        // Without `STAT`, the `fvar` order is preserved.
+        let tags: std::vec::Vec<_> = StatOrderedVariationAxes::new(table.axes(), None)
+            .map(|axis| axis.tag).collect();
+        assert_eq!(tags, vec![Tag::from_bytes(b"wght"), Tag::from_bytes(b"wdth")]);
+    }
+
+    #[test]
+    fn axes_ordered_by_stat() {
+        let stat_data = &[
+            0x00, 0x01, // major version: 1
+            0x00, 0x01, // minor version: 1
+            0x00, 0x08, // design axis size: 8
+            0x00, 0x02, // design axis count: 2
+            0x00, 0x00, 0x00, 0x14, // design axes offset: 20
+            0x00, 0x00, // axis value count: 0
+            0x00, 0x00, 0x00, 0x00, // axis value offsets offset: 0
+            0x00, 0x02, // elided fallback name ID: 2
+            // Design Axis [0]
+            0x77, 0x67, 0x68, 0x74, // tag: wght
+            0x01, 0x00, // axis name ID: 256
+            0x00, 0x01, // axis ordering: 1
+            // Design Axis [1]
+            0x77, 0x64, 0x74, 0x68, // tag: wdth
+            0x01, 0x01, // axis name ID: 257
+            0x00, 0x00, // axis ordering: 0
+        ];
+
+        let table = Table::parse(TWO_AXES_DATA).unwrap();
+        let stat = stat::Table::parse(stat_data);
+        assert!(stat.is_some());
+        let tags: std::vec::Vec<_> = StatOrderedVariationAxes::new(table.axes(), stat)
+            .map(|axis| axis.tag).collect();
+        assert_eq!(tags, vec![Tag::from_bytes(b"wdth"), Tag::from_bytes(b"wght")]);
+    }
+}
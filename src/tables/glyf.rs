@@ -474,6 +474,7 @@ impl SimpleGlyphFlags {
     #[inline] fn repeat_flag(self) -> bool { self.0 & 0x08 != 0 }
     #[inline] fn x_is_same_or_positive_short(self) -> bool { self.0 & 0x10 != 0 }
     #[inline] fn y_is_same_or_positive_short(self) -> bool { self.0 & 0x20 != 0 }
+    #[inline] fn overlap_simple(self) -> bool { self.0 & 0x40 != 0 }
 }
 
 
@@ -488,6 +489,8 @@ impl CompositeGlyphFlags {
     #[inline] pub fn more_components(self) -> bool { self.0 & 0x0020 != 0 }
     #[inline] pub fn we_have_an_x_and_y_scale(self) -> bool { self.0 & 0x0040 != 0 }
     #[inline] pub fn we_have_a_two_by_two(self) -> bool { self.0 & 0x0080 != 0 }
+    #[inline] pub fn use_my_metrics(self) -> bool { self.0 & 0x0200 != 0 }
+    #[inline] pub fn overlap_compound(self) -> bool { self.0 & 0x0400 != 0 }
 }
 
 
@@ -526,6 +529,143 @@ pub(crate) fn glyph_bbox(
     })
 }
 
+/// Returns the last point index of each contour of a simple glyph.
+///
+/// Returns `None` for composite and empty glyphs.
+pub(crate) fn glyph_contour_endpoints<'a>(
+    loca_table: loca::Table,
+    glyf_table: &'a [u8],
+    glyph_id: GlyphId,
+) -> Option<LazyArray16<'a, u16>> {
+    let range = loca_table.glyph_range(glyph_id)?;
+    let glyph_data = glyf_table.get(range)?;
+    let mut s = Stream::new(glyph_data);
+    let number_of_contours: i16 = s.read()?;
+    if number_of_contours <= 0 {
+        return None;
+    }
+
+    s.advance(8); // bbox
+    // u16 casting is safe, since we already checked that the value is positive.
+    s.read_array16::<u16>(number_of_contours as u16)
+}
+
+/// Returns the number of contours of a glyph with all components expanded.
+///
+/// Returns `Some(0)` for empty glyphs. Glyph ID must be checked by the caller,
+/// since `loca` doesn't distinguish empty and out of bounds glyphs.
+pub(crate) fn glyph_total_contours(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    glyph_id: GlyphId,
+) -> Option<u16> {
+    total_contours_impl(loca_table, glyf_table, glyph_id, 0)
+}
+
+fn total_contours_impl(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    glyph_id: GlyphId,
+    depth: u8,
+) -> Option<u16> {
+    if depth >= MAX_COMPONENTS {
+        return None;
+    }
+
+    let range = match loca_table.glyph_range(glyph_id) {
+        Some(range) => range,
+        // An empty glyph.
+        None => return Some(0),
+    };
+
+    let glyph_data = glyf_table.get(range)?;
+    let mut s = Stream::new(glyph_data);
+    let number_of_contours: i16 = s.read()?;
+    if number_of_contours >= 0 {
+        // u16 casting is safe, since we already checked that the value is not negative.
+        return Some(number_of_contours as u16);
+    }
+
+    s.advance(8); // bbox
+    let mut total: u16 = 0;
+    for comp in CompositeGlyphIter::new(s.tail()?) {
+        let count = total_contours_impl(loca_table, glyf_table, comp.glyph_id, depth + 1)?;
+        total = total.checked_add(count)?;
+    }
+
+    Some(total)
+}
+
+/// Returns a glyph which metrics should be used for the specified glyph.
+///
+/// When a component of a composite glyph has the `USE_MY_METRICS` flag set,
+/// the composite glyph must use the metrics of this component.
+/// Nested composite glyphs are resolved too.
+/// Otherwise, the specified glyph is returned.
+pub(crate) fn metrics_glyph(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    mut glyph_id: GlyphId,
+) -> GlyphId {
+    for _ in 0..MAX_COMPONENTS {
+        match use_my_metrics_component(loca_table, glyf_table, glyph_id) {
+            Some(id) => glyph_id = id,
+            None => break,
+        }
+    }
+
+    glyph_id
+}
+
+fn use_my_metrics_component(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    glyph_id: GlyphId,
+) -> Option<GlyphId> {
+    let range = loca_table.glyph_range(glyph_id)?;
+    let glyph_data = glyf_table.get(range)?;
+    let mut s = Stream::new(glyph_data);
+    let number_of_contours: i16 = s.read()?;
+    if number_of_contours >= 0 {
+        return None;
+    }
+
+    s.advance(8); // bbox
+    CompositeGlyphIter::new(s.tail()?)
+        .find(|comp| comp.flags.use_my_metrics())
+        .map(|comp| comp.glyph_id)
+}
+
+/// Checks that a glyph has the `OVERLAP_SIMPLE` or `OVERLAP_COMPOUND` flag set.
+///
+/// `OVERLAP_SIMPLE` is checked only on the first point flag, as required by the spec.
+/// `OVERLAP_COMPOUND` is checked on all components, since some fonts set it not only
+/// on the first one. Nested components are not checked.
+pub(crate) fn glyph_has_overlap_flag(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    glyph_id: GlyphId,
+) -> Option<bool> {
+    let range = loca_table.glyph_range(glyph_id)?;
+    let glyph_data = glyf_table.get(range)?;
+    let mut s = Stream::new(glyph_data);
+    let number_of_contours: i16 = s.read()?;
+    s.advance(8); // bbox
+
+    if number_of_contours > 0 {
+        // u16 casting is safe, since we already checked that the value is positive.
+        s.advance(usize::from(number_of_contours as u16) * 2); // endPtsOfContours
+        let instructions_len: u16 = s.read()?;
+        s.advance(usize::from(instructions_len));
+        Some(SimpleGlyphFlags(s.read::<u8>()?).overlap_simple())
+    } else if number_of_contours < 0 {
+        Some(CompositeGlyphIter::new(s.tail()?).any(|comp| comp.flags.overlap_compound()))
+    } else {
+        // An empty glyph.
+        None
+    }
+}
+
 #[inline]
 fn outline_impl(
     loca_table: loca::Table,
@@ -660,3 +800,66 @@ fn resolve_coords_len(
 
     Some((x_coords_len, y_coords_len))
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndexToLocationFormat;
+
+    #[test]
+    fn overlap_flag() {
+        let loca_data = &[
+            0x00, 0x00, // offset [0]: 0
+            0x00, 0x0C, // offset [1]: 24
+            0x00, 0x18, // offset [2]: 48
+            0x00, 0x23, // offset [3]: 70
+        ];
+
+        let glyf_data = &[
+            // Glyph [0]
+            0x00, 0x01, // number of contours: 1
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x0A, // bbox: 0 0 10 10
+            0x00, 0x02, // end point [0]: 2
+            0x00, 0x00, // instructions length: 0
+            0x01, // flags [0]: on curve
+            0x33, // flags [1]: on curve | x short | x is positive | y is same
+            0x35, // flags [2]: on curve | y short | x is same | y is positive
+            0x00, 0x00, // x [0]: 0
+            0x0A, // x [1]: 10
+            0x00, 0x00, // y [0]: 0
+            0x0A, // y [2]: 10
+            0x00, // padding
+            // Glyph [1]
+            0x00, 0x01, // number of contours: 1
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x0A, // bbox: 0 0 10 10
+            0x00, 0x02, // end point [0]: 2
+            0x00, 0x00, // instructions length: 0
+            0x41, // flags [0]: on curve | overlap simple
+            0x33, // flags [1]: on curve | x short | x is positive | y is same
+            0x35, // flags [2]: on curve | y short | x is same | y is positive
+            0x00, 0x00, // x [0]: 0
+            0x0A, // x [1]: 10
+            0x00, 0x00, // y [0]: 0
+            0x0A, // y [2]: 10
+            0x00, // padding
+            // Glyph [2]
+            0xFF, 0xFF, // number of contours: -1
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x0A, // bbox: 0 0 10 10
+            0x00, 0x22, // component [0] flags: more components | args are xy values
+            0x00, 0x00, // component [0] glyph ID: 0
+            0x00, 0x00, // component [0] offsets: 0 0
+            0x04, 0x02, // component [1] flags: overlap compound | args are xy values
+            0x00, 0x01, // component [1] glyph ID: 1
+            0x00, 0x00, // component [1] offsets: 0 0
+        ];
+
+        let number_of_glyphs = NonZeroU16::new(3).unwrap();
+        let loca_table = loca::Table::parse(loca_data, number_of_glyphs,
+                                            IndexToLocationFormat::Short).unwrap();
+        assert_eq!(glyph_has_overlap_flag(loca_table, glyf_data, GlyphId(0)), Some(false));
+        assert_eq!(glyph_has_overlap_flag(loca_table, glyf_data, GlyphId(1)), Some(true));
+        assert_eq!(glyph_has_overlap_flag(loca_table, glyf_data, GlyphId(2)), Some(true));
+        assert_eq!(glyph_has_overlap_flag(loca_table, glyf_data, GlyphId(3)), None);
+    }
+}
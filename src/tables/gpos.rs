@@ -0,0 +1,378 @@
+/*!
+A [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos)
+implementation.
+
+This module provides a low-level access to GPOS scripts, features and lookups.
+It's up to the caller to implement a shaping algorithm on top of it.
+*/
+
+use crate::GlyphId;
+use crate::ggg::*;
+use crate::parser::{Stream, FromData, Offset16, Offset, LazyArray16};
+
+const EXTENSION_POSITIONING: u16 = 9;
+
+
+/// A [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Gpos<'a>(GsubGposTable<'a>);
+
+impl<'a> Gpos<'a> {
+    /// Parses a table from raw data.
+    #[inline]
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        GsubGposTable::parse(data, EXTENSION_POSITIONING).map(Gpos)
+    }
+}
+
+impl<'a> GlyphPosSubTable for Gpos<'a> {
+    #[inline]
+    fn version(&self) -> u32 {
+        self.0.version()
+    }
+
+    #[inline]
+    fn has_feature_variations(&self) -> bool {
+        self.0.has_feature_variations()
+    }
+
+    #[inline]
+    fn scripts(&self) -> Scripts<'_> {
+        self.0.scripts()
+    }
+
+    #[inline]
+    fn script_at(&self, index: ScriptIndex) -> Option<Script<'_>> {
+        self.0.script_at(index)
+    }
+
+    #[inline]
+    fn features(&self) -> Features<'_> {
+        self.0.features()
+    }
+
+    #[inline]
+    fn feature_at(&self, index: FeatureIndex) -> Option<Feature<'_>> {
+        self.0.feature_at(index)
+    }
+
+    #[inline]
+    fn lookups(&self) -> Lookups<'_> {
+        self.0.lookups()
+    }
+
+    #[inline]
+    fn lookup_at(&self, index: LookupIndex) -> Option<Lookup<'_>> {
+        self.0.lookup_at(index)
+    }
+
+    #[inline]
+    fn feature_variations(&self) -> FeatureVariations<'_> {
+        self.0.feature_variations()
+    }
+
+    #[inline]
+    fn feature_variation_at(&self, index: FeatureVariationIndex) -> Option<FeatureVariation<'_>> {
+        self.0.feature_variation_at(index)
+    }
+}
+
+
+/// A [Value Record](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#value-record).
+///
+/// Device and VariationIndex tables are ignored, so values are for the default instance
+/// and are not adjusted for a specific size.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct ValueRecord {
+    /// Horizontal adjustment for placement, in design units.
+    pub x_placement: i16,
+    /// Vertical adjustment for placement, in design units.
+    pub y_placement: i16,
+    /// Horizontal adjustment for advance, in design units.
+    pub x_advance: i16,
+    /// Vertical adjustment for advance, in design units.
+    pub y_advance: i16,
+}
+
+
+/// A Value Format, which defines what fields are present in a Value Record.
+#[derive(Clone, Copy)]
+struct ValueFormat(u16);
+
+impl ValueFormat {
+    #[inline] fn x_placement(self) -> bool { self.0 & 0x0001 != 0 }
+    #[inline] fn y_placement(self) -> bool { self.0 & 0x0002 != 0 }
+    #[inline] fn x_advance(self) -> bool { self.0 & 0x0004 != 0 }
+    #[inline] fn y_advance(self) -> bool { self.0 & 0x0008 != 0 }
+
+    /// Returns the size of a Value Record in bytes.
+    ///
+    /// Includes Device table offsets, which we do not parse.
+    #[inline]
+    fn size(self) -> usize {
+        (self.0 & 0x00FF).count_ones() as usize * 2
+    }
+
+    fn parse(self, data: &[u8]) -> Option<ValueRecord> {
+        let mut s = Stream::new(data.get(0..self.size())?);
+        let mut record = ValueRecord::default();
+        if self.x_placement() { record.x_placement = s.read()?; }
+        if self.y_placement() { record.y_placement = s.read()?; }
+        if self.x_advance() { record.x_advance = s.read()?; }
+        if self.y_advance() { record.y_advance = s.read()?; }
+        Some(record)
+    }
+}
+
+
+#[derive(Clone, Copy)]
+enum PairAdjustmentFormat<'a> {
+    Format1 {
+        // Pair set offsets are from the start of the subtable.
+        data: &'a [u8],
+        pair_sets: LazyArray16<'a, Offset16>,
+    },
+    Format2 {
+        classes1: ClassDefinitionTable<'a>,
+        classes2: ClassDefinitionTable<'a>,
+        class2_count: u16,
+        // An array of `class1_count * class2_count` pairs of Value Records.
+        records: &'a [u8],
+    },
+}
+
+
+/// A [Pair Adjustment Positioning Subtable](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#lookup-type-2-pair-adjustment-positioning-subtable).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct PairAdjustment<'a> {
+    coverage: CoverageTable<'a>,
+    value_format1: ValueFormat,
+    value_format2: ValueFormat,
+    format: PairAdjustmentFormat<'a>,
+}
+
+impl<'a> PairAdjustment<'a> {
+    /// Parses a pair adjustment subtable from raw data.
+    ///
+    /// `data` must start at the subtable, not at the lookup.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format: u16 = s.read()?;
+        let coverage_offset: Offset16 = s.read()?;
+        let coverage = CoverageTable::new(data.get(coverage_offset.to_usize()..)?);
+        let value_format1 = ValueFormat(s.read()?);
+        let value_format2 = ValueFormat(s.read()?);
+        let format = match format {
+            1 => {
+                let count: u16 = s.read()?;
+                let pair_sets = s.read_array16::<Offset16>(count)?;
+                PairAdjustmentFormat::Format1 { data, pair_sets }
+            }
+            2 => {
+                let classes1_offset: Offset16 = s.read()?;
+                let classes2_offset: Offset16 = s.read()?;
+                let class1_count: u16 = s.read()?;
+                let class2_count: u16 = s.read()?;
+                let record_size = value_format1.size() + value_format2.size();
+                let len = usize::from(class1_count)
+                    .checked_mul(usize::from(class2_count))?
+                    .checked_mul(record_size)?;
+                PairAdjustmentFormat::Format2 {
+                    classes1: ClassDefinitionTable::new(data.get(classes1_offset.to_usize()..)?),
+                    classes2: ClassDefinitionTable::new(data.get(classes2_offset.to_usize()..)?),
+                    class2_count,
+                    records: s.read_bytes(len)?,
+                }
+            }
+            _ => return None,
+        };
+
+        Some(PairAdjustment { coverage, value_format1, value_format2, format })
+    }
+
+    /// Returns adjustments for a glyph pair.
+    ///
+    /// The first Value Record adjusts the first glyph and the second one adjusts
+    /// the second glyph. Fields that are not present in the subtable are set to zero.
+    /// Kerning usually needs only `x_advance` of the first record.
+    ///
+    /// Returns `None` when the pair is not covered by this subtable.
+    pub fn get(&self, first: GlyphId, second: GlyphId) -> Option<(ValueRecord, ValueRecord)> {
+        let index = self.coverage.get(first)?;
+        let size1 = self.value_format1.size();
+        let record_size = size1 + self.value_format2.size();
+        let record = match self.format {
+            PairAdjustmentFormat::Format1 { data, pair_sets } => {
+                let pair_set = data.get(pair_sets.get(index)?.to_usize()..)?;
+                find_pair_value_record(pair_set, second, record_size)?
+            }
+            PairAdjustmentFormat::Format2 { classes1, classes2, class2_count, records } => {
+                let class1 = classes1.get(first).0;
+                let class2 = classes2.get(second).0;
+                if class2 >= class2_count {
+                    return None;
+                }
+
+                let index = usize::from(class1) * usize::from(class2_count) + usize::from(class2);
+                let start = index * record_size;
+                records.get(start..start + record_size)?
+            }
+        };
+
+        Some((self.value_format1.parse(record)?, self.value_format2.parse(record.get(size1..)?)?))
+    }
+}
+
+/// Finds the Value Records of a second glyph in a Pair Set.
+///
+/// Pair Value Records have a variable size, so we cannot use `LazyArray16`.
+fn find_pair_value_record(data: &[u8], second: GlyphId, values_size: usize) -> Option<&[u8]> {
+    let mut s = Stream::new(data);
+    let count: u16 = s.read()?;
+    let record_size = GlyphId::SIZE + values_size;
+    let records = s.read_bytes(usize::from(count) * record_size)?;
+
+    // Records are sorted by the second glyph ID.
+    let mut start = 0;
+    let mut end = usize::from(count);
+    while start < end {
+        let mid = (start + end) / 2;
+        let offset = mid * record_size;
+        let glyph_id: GlyphId = Stream::read_at(records, offset)?;
+        if glyph_id < second {
+            start = mid + 1;
+        } else if glyph_id > second {
+            end = mid;
+        } else {
+            return records.get(offset + GlyphId::SIZE..offset + record_size);
+        }
+    }
+
+    None
+}
+
+
+/// Checks that any coverage table of a lookup subtable contains a glyph.
+///
+/// Glyphs that are referenced only via class definitions or as the second glyph
+/// of a pair are not detected.
+pub(crate) fn subtable_contains(lookup_type: u16, data: &[u8], glyph_id: GlyphId) -> bool {
+    let (lookup_type, data) = if lookup_type == EXTENSION_POSITIONING {
+        try_opt_or!(resolve_extension(data), false)
+    } else {
+        (lookup_type, data)
+    };
+
+    let kind = match lookup_type {
+        7 => SubtableKind::Context,
+        8 => SubtableKind::ChainContext,
+        _ => SubtableKind::Simple,
+    };
+
+    if subtable_coverage_contains(data, kind, glyph_id) {
+        return true;
+    }
+
+    match lookup_type {
+        // Mark-to-Base, Mark-to-Ligature and Mark-to-Mark have a second coverage.
+        4..=6 => {
+            let offset = try_opt_or!(Stream::read_at::<Offset16>(data, 4), false);
+            let coverage = try_opt_or!(data.get(offset.to_usize()..), false);
+            CoverageTable::new(coverage).contains(glyph_id)
+        }
+        _ => false,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pair_format1() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x0C, // coverage offset: 12
+            0x00, 0x04, // value format 1: x advance
+            0x00, 0x05, // value format 2: x placement and x advance
+            0x00, 0x01, // pair set count: 1
+            0x00, 0x12, // pair set offset [0]: 18
+            // Coverage
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x05, // glyph [0]: 5
+            // Pair Set [0]
+            0x00, 0x02, // pair value count: 2
+            // Pair Value Record [0]
+            0x00, 0x07, // second glyph: 7
+            0xFF, 0xF6, // value record 1 x advance: -10
+            0x00, 0x00, // value record 2 x placement: 0
+            0x00, 0x00, // value record 2 x advance: 0
+            // Pair Value Record [1]
+            0x00, 0x09, // second glyph: 9
+            0xFF, 0xEC, // value record 1 x advance: -20
+            0x00, 0x03, // value record 2 x placement: 3
+            0x00, 0x05, // value record 2 x advance: 5
+        ];
+
+        let subtable = PairAdjustment::parse(data).unwrap();
+        let (v1, v2) = subtable.get(GlyphId(5), GlyphId(7)).unwrap();
+        assert_eq!(v1, ValueRecord { x_advance: -10, ..ValueRecord::default() });
+        assert_eq!(v2, ValueRecord::default());
+
+        let (v1, v2) = subtable.get(GlyphId(5), GlyphId(9)).unwrap();
+        assert_eq!(v1, ValueRecord { x_advance: -20, ..ValueRecord::default() });
+        assert_eq!(v2, ValueRecord { x_placement: 3, x_advance: 5, ..ValueRecord::default() });
+
+        assert!(subtable.get(GlyphId(5), GlyphId(8)).is_none());
+        assert!(subtable.get(GlyphId(7), GlyphId(9)).is_none());
+    }
+
+    #[test]
+    fn pair_format2() {
+        let data = &[
+            0x00, 0x02, // format: 2
+            0x00, 0x20, // coverage offset: 32
+            0x00, 0x04, // value format 1: x advance
+            0x00, 0x04, // value format 2: x advance
+            0x00, 0x26, // class definition 1 offset: 38
+            0x00, 0x2E, // class definition 2 offset: 46
+            0x00, 0x02, // class 1 count: 2
+            0x00, 0x02, // class 2 count: 2
+            // Class 1 Record [0]
+            0x00, 0x00, 0x00, 0x00, // class 2 record [0]: 0, 0
+            0x00, 0x00, 0x00, 0x00, // class 2 record [1]: 0, 0
+            // Class 1 Record [1]
+            0x00, 0x00, 0x00, 0x00, // class 2 record [0]: 0, 0
+            0xFF, 0xF6, 0x00, 0x0A, // class 2 record [1]: -10, 10
+            // Coverage
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x05, // glyph [0]: 5
+            // Class Definition 1
+            0x00, 0x01, // format: 1
+            0x00, 0x05, // start glyph ID: 5
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x01, // class [0]: 1
+            // Class Definition 2
+            0x00, 0x01, // format: 1
+            0x00, 0x07, // start glyph ID: 7
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x01, // class [0]: 1
+        ];
+
+        let subtable = PairAdjustment::parse(data).unwrap();
+        let (v1, v2) = subtable.get(GlyphId(5), GlyphId(7)).unwrap();
+        assert_eq!(v1.x_advance, -10);
+        assert_eq!(v2.x_advance, 10);
+
+        let (v1, v2) = subtable.get(GlyphId(5), GlyphId(8)).unwrap();
+        assert_eq!(v1.x_advance, 0);
+        assert_eq!(v2.x_advance, 0);
+
+        assert!(subtable.get(GlyphId(6), GlyphId(7)).is_none());
+    }
+}
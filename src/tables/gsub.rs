@@ -0,0 +1,890 @@
+/*!
+A [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub)
+implementation.
+
+This module provides a low-level access to GSUB lookup subtables.
+It's up to the caller to implement a shaping algorithm on top of it.
+*/
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::{GlyphId, Tag};
+use crate::ggg::*;
+use crate::parser::{Stream, Offset16, Offset, LazyArray16, Offsets16, OffsetsIter16};
+
+const EXTENSION_SUBSTITUTION: u16 = 7;
+
+
+/// A [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Gsub<'a>(GsubGposTable<'a>);
+
+impl<'a> Gsub<'a> {
+    /// Parses a table from raw data.
+    #[inline]
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        GsubGposTable::parse(data, EXTENSION_SUBSTITUTION).map(Gsub)
+    }
+
+    /// Returns an iterator over all ligatures of a feature.
+    ///
+    /// Ligatures from all lookups of all features with the specified tag are yielded
+    /// in the lookup list order, regardless of a script and a language.
+    /// Each lookup is visited once, even when it's used by multiple features.
+    ///
+    /// Identical ligatures are yielded once, even when they are stored in different
+    /// subtables or lookups. Since the iterator doesn't allocate, each ligature is checked
+    /// against the previous subtables, which makes iteration quadratic.
+    #[inline]
+    pub fn ligatures(&self, feature: Tag) -> Ligatures<'a> {
+        Ligatures::new(self.0.feature_list(), self.0.lookup_list(), feature)
+    }
+
+    /// Returns a ligature glyph for the exact sequence of components.
+    ///
+    /// Ligature substitutions from all lookups of all features with the specified tag
+    /// are checked in the lookup list order, regardless of a script and a language.
+    /// Unlike `debug_apply`, the ligature must consume all `components`.
+    pub fn ligature_for(&self, components: &[GlyphId], feature: Tag) -> Option<GlyphId> {
+        let (first, rest) = components.split_first()?;
+        LigatureSubtables::new(self.0.feature_list(), self.0.lookup_list(), feature)
+            .find_map(|subtable| {
+                subtable.ligatures(*first)?
+                    .find(|l| l.components.into_iter().eq(rest.iter().cloned()))
+            })
+            .map(|ligature| ligature.glyph)
+    }
+
+    /// Applies all lookups of a feature to a glyph sequence.
+    ///
+    /// **This is not a shaper** and should be used only for testing and debugging.
+    ///
+    /// Lookups of all features with the specified tag are applied once, in the lookup list order,
+    /// regardless of a script and a language. Only single, multiple and ligature
+    /// substitutions are supported. Lookup flags are ignored.
+    #[cfg(feature = "std")]
+    pub fn debug_apply(&self, glyphs: &[GlyphId], feature: Tag) -> Vec<GlyphId> {
+        let mut lookups: Vec<LookupIndex> = self.features()
+            .filter(|f| f.tag() == feature)
+            .flat_map(|f| f.lookup_indices().into_iter())
+            .collect();
+        lookups.sort();
+        lookups.dedup();
+
+        let mut glyphs = glyphs.to_vec();
+        for lookup in lookups.into_iter().filter_map(|index| self.lookup_at(index)) {
+            let mut output = Vec::with_capacity(glyphs.len());
+            let mut i = 0;
+            while i < glyphs.len() {
+                let applied = lookup.subtables().find_map(|data| {
+                    debug_apply_subtable(lookup.lookup_type(), data, &glyphs[i..])
+                });
+
+                match applied {
+                    Some((consumed, substitutes)) => {
+                        output.extend_from_slice(&substitutes);
+                        i += consumed;
+                    }
+                    None => {
+                        output.push(glyphs[i]);
+                        i += 1;
+                    }
+                }
+            }
+
+            glyphs = output;
+        }
+
+        glyphs
+    }
+}
+
+impl<'a> GlyphPosSubTable for Gsub<'a> {
+    #[inline]
+    fn version(&self) -> u32 {
+        self.0.version()
+    }
+
+    #[inline]
+    fn has_feature_variations(&self) -> bool {
+        self.0.has_feature_variations()
+    }
+
+    #[inline]
+    fn scripts(&self) -> Scripts<'_> {
+        self.0.scripts()
+    }
+
+    #[inline]
+    fn script_at(&self, index: ScriptIndex) -> Option<Script<'_>> {
+        self.0.script_at(index)
+    }
+
+    #[inline]
+    fn features(&self) -> Features<'_> {
+        self.0.features()
+    }
+
+    #[inline]
+    fn feature_at(&self, index: FeatureIndex) -> Option<Feature<'_>> {
+        self.0.feature_at(index)
+    }
+
+    #[inline]
+    fn lookups(&self) -> Lookups<'_> {
+        self.0.lookups()
+    }
+
+    #[inline]
+    fn lookup_at(&self, index: LookupIndex) -> Option<Lookup<'_>> {
+        self.0.lookup_at(index)
+    }
+
+    #[inline]
+    fn feature_variations(&self) -> FeatureVariations<'_> {
+        self.0.feature_variations()
+    }
+
+    #[inline]
+    fn feature_variation_at(&self, index: FeatureVariationIndex) -> Option<FeatureVariation<'_>> {
+        self.0.feature_variation_at(index)
+    }
+}
+
+
+/// A ligature from a [Ligature Substitution Subtable](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub#LS).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Ligature<'a> {
+    /// The first component glyph.
+    ///
+    /// Stored in the subtable's coverage.
+    pub first_component: GlyphId,
+    /// The remaining component glyphs.
+    pub components: LazyArray16<'a, GlyphId>,
+    /// A ligature glyph.
+    pub glyph: GlyphId,
+}
+
+impl<'a> Ligature<'a> {
+    fn parse(first_component: GlyphId, data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let glyph: GlyphId = s.read()?;
+        let count: u16 = s.read()?;
+        // The first component is stored in the coverage,
+        // so a zero count is invalid and such ligature will be skipped.
+        let components = s.read_array16::<GlyphId>(count.checked_sub(1)?)?;
+        Some(Ligature { first_component, components, glyph })
+    }
+}
+
+impl PartialEq for Ligature<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.first_component == other.first_component
+            && self.glyph == other.glyph
+            && self.components.into_iter().eq(other.components)
+    }
+}
+
+
+/// An iterator over ligatures of a feature.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Ligatures<'a> {
+    // All subtables, used to find already yielded ligatures.
+    all_subtables: LigatureSubtables<'a>,
+    subtables: LigatureSubtables<'a>,
+    // The number of subtables read so far, including the current one.
+    subtable_count: usize,
+    coverage: CoverageGlyphs<'a>,
+    sets: Offsets16<'a, Offset16>,
+    set_index: u16,
+    first_component: GlyphId,
+    // Ligatures of the current ligature set.
+    ligatures: Offsets16<'a, Offset16>,
+    ligature_index: u16,
+}
+
+impl<'a> Ligatures<'a> {
+    fn new(features: Features<'a>, lookups: Lookups<'a>, feature: Tag) -> Self {
+        let subtables = LigatureSubtables::new(features, lookups, feature);
+        Ligatures {
+            all_subtables: subtables,
+            subtables,
+            subtable_count: 0,
+            coverage: CoverageGlyphs::default(),
+            sets: Offsets16::default(),
+            set_index: 0,
+            first_component: GlyphId(0),
+            ligatures: Offsets16::default(),
+            ligature_index: 0,
+        }
+    }
+
+    fn ligature_at(&self, index: u16) -> Option<Ligature<'a>> {
+        Ligature::parse(self.first_component, self.ligatures.get(index)?)
+    }
+
+    /// Checks that a ligature at `index` in the current ligature set was already yielded.
+    fn is_duplicate(&self, ligature: &Ligature<'a>, index: u16) -> bool {
+        let in_set = (0..index)
+            .filter_map(|i| self.ligature_at(i))
+            .any(|prev| prev == *ligature);
+
+        in_set || self.all_subtables.take(self.subtable_count - 1).any(|subtable| {
+            subtable.ligatures(ligature.first_component)
+                .map(|mut ligatures| ligatures.any(|prev| prev == *ligature))
+                .unwrap_or(false)
+        })
+    }
+}
+
+impl Default for Ligatures<'_> {
+    #[inline]
+    fn default() -> Self {
+        Ligatures::new(Features::default(), Lookups::default(), Tag(0))
+    }
+}
+
+impl<'a> Iterator for Ligatures<'a> {
+    type Item = Ligature<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.ligature_index < self.ligatures.len() {
+                let index = self.ligature_index;
+                self.ligature_index += 1;
+                let ligature = match self.ligature_at(index) {
+                    Some(ligature) => ligature,
+                    None => continue,
+                };
+
+                if !self.is_duplicate(&ligature, index) {
+                    return Some(ligature);
+                }
+            } else if let Some(glyph_id) = self.coverage.next() {
+                // Ligature sets are in the coverage order.
+                self.first_component = glyph_id;
+                self.ligatures = self.sets.get(self.set_index)
+                    .and_then(parse_ligature_set)
+                    .unwrap_or_default();
+                self.ligature_index = 0;
+                self.set_index += 1;
+            } else {
+                let subtable = self.subtables.next()?;
+                self.coverage = subtable.coverage.glyphs();
+                self.sets = subtable.sets;
+                self.set_index = 0;
+                self.subtable_count += 1;
+            }
+        }
+    }
+}
+
+
+/// A [Ligature Substitution Subtable](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub#LS).
+#[derive(Clone, Copy)]
+struct LigatureSubstitution<'a> {
+    coverage: CoverageTable<'a>,
+    // Ligature sets in the coverage order.
+    sets: Offsets16<'a, Offset16>,
+}
+
+impl<'a> LigatureSubstitution<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format: u16 = s.read()?;
+        if format != 1 {
+            return None;
+        }
+
+        let coverage_offset: Offset16 = s.read()?;
+        let coverage = CoverageTable::new(data.get(coverage_offset.to_usize()..)?);
+        let count: u16 = s.read()?;
+        let sets = s.read_offsets16::<Offset16>(count, data)?;
+        Some(LigatureSubstitution { coverage, sets })
+    }
+
+    /// Returns ligatures that start with the specified glyph.
+    fn ligatures(&self, first: GlyphId) -> Option<impl Iterator<Item = Ligature<'a>>> {
+        let index = self.coverage.get(first)?;
+        let ligatures = parse_ligature_set(self.sets.get(index)?)?;
+        Some(ligatures.into_iter().filter_map(move |data| Ligature::parse(first, data)))
+    }
+}
+
+/// Parses a Ligature Set table into offsets to ligatures.
+fn parse_ligature_set(data: &[u8]) -> Option<Offsets16<'_, Offset16>> {
+    let mut s = Stream::new(data);
+    let count: u16 = s.read()?;
+    s.read_offsets16::<Offset16>(count, data)
+}
+
+
+/// An iterator over Ligature Substitution Subtables used by all features with a specific tag.
+///
+/// Subtables are returned in the lookup list order. Extension subtables are resolved
+/// and malformed subtables are skipped.
+#[derive(Clone, Copy)]
+struct LigatureSubtables<'a> {
+    lookups: Lookups<'a>,
+    lookup_indices: FeatureLookups<'a>,
+    lookup_type: u16,
+    subtables: OffsetsIter16<'a, Offset16>,
+}
+
+impl<'a> LigatureSubtables<'a> {
+    fn new(features: Features<'a>, lookups: Lookups<'a>, feature: Tag) -> Self {
+        LigatureSubtables {
+            lookups,
+            lookup_indices: FeatureLookups::new(features, lookups, feature),
+            lookup_type: 0,
+            subtables: OffsetsIter16::default(),
+        }
+    }
+}
+
+impl<'a> Iterator for LigatureSubtables<'a> {
+    type Item = LigatureSubstitution<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(data) = self.subtables.next() {
+                if let Some((4, data)) = resolve_subtable(self.lookup_type, data) {
+                    if let Some(subtable) = LigatureSubstitution::parse(data) {
+                        return Some(subtable);
+                    }
+                }
+            } else {
+                let index = self.lookup_indices.next()?;
+                if let Some(lookup) = self.lookups.get(index) {
+                    self.lookup_type = lookup.lookup_type();
+                    self.subtables = lookup.subtables();
+                }
+            }
+        }
+    }
+}
+
+
+#[derive(Clone, Copy)]
+enum SingleSubstitutionFormat<'a> {
+    Format1 {
+        delta: i16,
+    },
+    Format2 {
+        substitutes: LazyArray16<'a, GlyphId>,
+    },
+}
+
+
+/// A [Single Substitution Subtable](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub#SS).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct SingleSubstitution<'a> {
+    coverage: CoverageTable<'a>,
+    format: SingleSubstitutionFormat<'a>,
+}
+
+impl<'a> SingleSubstitution<'a> {
+    /// Parses a single substitution subtable from raw data.
+    ///
+    /// `data` must start at the subtable, not at the lookup.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format: u16 = s.read()?;
+        let coverage_offset: Offset16 = s.read()?;
+        let coverage = CoverageTable::new(data.get(coverage_offset.to_usize()..)?);
+        let format = match format {
+            1 => {
+                let delta: i16 = s.read()?;
+                SingleSubstitutionFormat::Format1 { delta }
+            }
+            2 => {
+                let count: u16 = s.read()?;
+                let substitutes = s.read_array16::<GlyphId>(count)?;
+                SingleSubstitutionFormat::Format2 { substitutes }
+            }
+            _ => return None,
+        };
+
+        Some(SingleSubstitution { coverage, format })
+    }
+
+    /// Returns a substitute for the specified glyph.
+    ///
+    /// Returns `None` when glyph is not covered by this subtable.
+    pub fn get(&self, glyph_id: GlyphId) -> Option<GlyphId> {
+        match self.format {
+            SingleSubstitutionFormat::Format1 { delta } => {
+                if self.coverage.contains(glyph_id) {
+                    // 'Addition of deltaGlyphID is modulo 65536.'
+                    Some(GlyphId(glyph_id.0.wrapping_add(delta as u16)))
+                } else {
+                    None
+                }
+            }
+            SingleSubstitutionFormat::Format2 { substitutes } => {
+                let index = self.coverage.get(glyph_id)?;
+                substitutes.get(index)
+            }
+        }
+    }
+
+    /// Substitutes all covered glyphs in a glyph buffer.
+    ///
+    /// Glyphs that are not covered by this subtable are left untouched.
+    ///
+    /// Only single substitution can be applied in-place. Multiple and ligature substitutions
+    /// change the number of glyphs and alternate substitution requires a caller to choose
+    /// an alternate, so they must be applied by the caller.
+    pub fn apply(&self, glyphs: &mut [GlyphId]) {
+        for glyph in glyphs {
+            if let Some(substitute) = self.get(*glyph) {
+                *glyph = substitute;
+            }
+        }
+    }
+}
+
+
+/// Applies a subtable at the start of a glyph sequence.
+///
+/// Returns the number of consumed glyphs and the substitutes.
+#[cfg(feature = "std")]
+fn debug_apply_subtable(
+    lookup_type: u16,
+    data: &[u8],
+    glyphs: &[GlyphId],
+) -> Option<(usize, Vec<GlyphId>)> {
+    let (lookup_type, data) = resolve_subtable(lookup_type, data)?;
+    let first = *glyphs.first()?;
+    let mut s = Stream::new(data);
+    s.skip::<u16>(); // format
+    let coverage_offset: Offset16 = s.read()?;
+    let coverage = CoverageTable::new(data.get(coverage_offset.to_usize()..)?);
+    match lookup_type {
+        // Single
+        1 => {
+            let substitute = SingleSubstitution::parse(data)?.get(first)?;
+            Some((1, vec![substitute]))
+        }
+        // Multiple
+        2 => {
+            let index = coverage.get(first)?;
+            let count: u16 = s.read()?;
+            let sequence = s.read_offsets16::<Offset16>(count, data)?.get(index)?;
+            let mut s = Stream::new(sequence);
+            let count: u16 = s.read()?;
+            let substitutes = s.read_array16::<GlyphId>(count)?;
+            Some((1, substitutes.into_iter().collect()))
+        }
+        // Ligature
+        4 => {
+            LigatureSubstitution::parse(data)?.ligatures(first)?.find_map(|ligature| {
+                let count = usize::from(ligature.components.len()) + 1;
+                let input = glyphs.get(1..count)?;
+                if ligature.components.into_iter().eq(input.iter().cloned()) {
+                    Some((count, vec![ligature.glyph]))
+                } else {
+                    None
+                }
+            })
+        }
+        _ => None,
+    }
+}
+
+
+/// Resolves an Extension Substitution subtable.
+///
+/// Returns the actual lookup type and subtable data. Other subtables are returned as is.
+fn resolve_subtable(lookup_type: u16, data: &[u8]) -> Option<(u16, &[u8])> {
+    if lookup_type == EXTENSION_SUBSTITUTION {
+        resolve_extension(data)
+    } else {
+        Some((lookup_type, data))
+    }
+}
+
+
+/// Checks that a lookup subtable references a glyph either as an input or as an output.
+pub(crate) fn subtable_contains(lookup_type: u16, data: &[u8], glyph_id: GlyphId) -> bool {
+    let (lookup_type, data) = try_opt_or!(resolve_subtable(lookup_type, data), false);
+    let kind = match lookup_type {
+        5 => SubtableKind::Context,
+        6 => SubtableKind::ChainContext,
+        _ => SubtableKind::Simple,
+    };
+
+    subtable_coverage_contains(data, kind, glyph_id)
+        || subtable_outputs_contain(lookup_type, data, glyph_id).unwrap_or(false)
+}
+
+// Checks glyphs that are not listed in the subtable's main coverage.
+fn subtable_outputs_contain(lookup_type: u16, data: &[u8], glyph_id: GlyphId) -> Option<bool> {
+    let contains = |glyphs: LazyArray16<GlyphId>| glyphs.into_iter().any(|g| g == glyph_id);
+
+    let mut s = Stream::new(data);
+    s.skip::<u16>(); // format
+    s.skip::<Offset16>(); // coverageOffset
+    match lookup_type {
+        // Single
+        1 => {
+            let subtable = SingleSubstitution::parse(data)?;
+            match subtable.format {
+                SingleSubstitutionFormat::Format1 { delta } => {
+                    let input = GlyphId(glyph_id.0.wrapping_sub(delta as u16));
+                    Some(subtable.coverage.contains(input))
+                }
+                SingleSubstitutionFormat::Format2 { substitutes } => {
+                    Some(contains(substitutes))
+                }
+            }
+        }
+        // Multiple and Alternate. Both store an array of offsets to glyph arrays.
+        2 | 3 => {
+            let count: u16 = s.read()?;
+            let offsets = s.read_offsets16::<Offset16>(count, data)?;
+            Some(offsets.into_iter().any(|data| {
+                let mut s = Stream::new(data);
+                s.read::<u16>()
+                    .and_then(|count| s.read_array16::<GlyphId>(count))
+                    .map(contains)
+                    .unwrap_or(false)
+            }))
+        }
+        // Ligature
+        4 => {
+            let subtable = LigatureSubstitution::parse(data)?;
+            // Malformed ligature sets and ligatures are skipped instead of ending the scan.
+            let found = subtable.sets.into_iter()
+                .filter_map(parse_ligature_set)
+                .flat_map(|ligatures| ligatures.into_iter())
+                // The first component is stored in the coverage, which is checked separately.
+                .filter_map(|data| Ligature::parse(GlyphId(0), data))
+                .any(|ligature| ligature.glyph == glyph_id || contains(ligature.components));
+            Some(found)
+        }
+        // Reverse Chaining Contextual Single
+        8 => {
+            for _ in 0..2 {
+                let count: u16 = s.read()?;
+                let offsets = s.read_offsets16::<Offset16>(count, data)?;
+                if coverages_contain(offsets, glyph_id) {
+                    return Some(true);
+                }
+            }
+
+            let count: u16 = s.read()?;
+            Some(contains(s.read_array16::<GlyphId>(count)?))
+        }
+        _ => Some(false),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_format1() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x06, // coverage offset: 6
+            0xFF, 0xFE, // delta glyph ID: -2
+            // Coverage
+            0x00, 0x01, // format: 1
+            0x00, 0x02, // glyph count: 2
+            0x00, 0x03, // glyph [0]: 3
+            0x00, 0x05, // glyph [1]: 5
+        ];
+
+        let subtable = SingleSubstitution::parse(data).unwrap();
+        let mut glyphs = [GlyphId(1), GlyphId(3), GlyphId(4), GlyphId(5)];
+        subtable.apply(&mut glyphs);
+        assert_eq!(glyphs, [GlyphId(1), GlyphId(1), GlyphId(4), GlyphId(3)]);
+    }
+
+    #[test]
+    fn single_format2() {
+        let data = &[
+            0x00, 0x02, // format: 2
+            0x00, 0x0A, // coverage offset: 10
+            0x00, 0x02, // glyph count: 2
+            0x00, 0x14, // substitute [0]: 20
+            0x00, 0x32, // substitute [1]: 50
+            // Coverage
+            0x00, 0x02, // format: 2
+            0x00, 0x02, // range count: 2
+            0x00, 0x02, // start glyph ID [0]: 2
+            0x00, 0x02, // end glyph ID [0]: 2
+            0x00, 0x00, // start coverage index [0]: 0
+            0x00, 0x05, // start glyph ID [1]: 5
+            0x00, 0x05, // end glyph ID [1]: 5
+            0x00, 0x01, // start coverage index [1]: 1
+        ];
+
+        let subtable = SingleSubstitution::parse(data).unwrap();
+        let mut glyphs = [GlyphId(1), GlyphId(2), GlyphId(3), GlyphId(5)];
+        subtable.apply(&mut glyphs);
+        assert_eq!(glyphs, [GlyphId(1), GlyphId(20), GlyphId(3), GlyphId(50)]);
+    }
+
+    #[test]
+    fn unknown_format() {
+        let data = &[
+            0x00, 0x03, // format: 3
+            0x00, 0x00, // coverage offset: 0
+        ];
+
+        assert!(SingleSubstitution::parse(data).is_none());
+    }
+
+    #[test]
+    fn ligature_output_is_referenced() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x08, // coverage offset: 8
+            0x00, 0x01, // ligature set count: 1
+            0x00, 0x0E, // ligature set offset [0]: 14
+            // Coverage
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x03, // glyph [0]: 3
+            // Ligature Set
+            0x00, 0x01, // ligature count: 1
+            0x00, 0x04, // ligature offset [0]: 4
+            // Ligature
+            0x00, 0x0A, // ligature glyph: 10
+            0x00, 0x02, // component count: 2
+            0x00, 0x05, // component [1]: 5
+        ];
+
+        // Only the first component is covered.
+        assert!(!subtable_coverage_contains(data, SubtableKind::Simple, GlyphId(10)));
+        assert!(subtable_contains(4, data, GlyphId(3)));
+        assert!(subtable_contains(4, data, GlyphId(5)));
+        assert!(subtable_contains(4, data, GlyphId(10)));
+        assert!(!subtable_contains(4, data, GlyphId(4)));
+    }
+
+    #[test]
+    fn ligature_output_after_malformed_ligature() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x08, // coverage offset: 8
+            0x00, 0x01, // ligature set count: 1
+            0x00, 0x0E, // ligature set offset [0]: 14
+            // Coverage
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x03, // glyph [0]: 3
+            // Ligature Set
+            0x00, 0x02, // ligature count: 2
+            0x00, 0x06, // ligature offset [0]: 6
+            0x00, 0x0A, // ligature offset [1]: 10
+            // Ligature [0]
+            0x00, 0x0B, // ligature glyph: 11
+            0x00, 0x00, // component count: 0 (malformed)
+            // Ligature [1]
+            0x00, 0x0A, // ligature glyph: 10
+            0x00, 0x02, // component count: 2
+            0x00, 0x05, // component [1]: 5
+        ];
+
+        // The malformed ligature is skipped, but doesn't stop the scan.
+        assert!(subtable_contains(4, data, GlyphId(10)));
+        assert!(subtable_contains(4, data, GlyphId(5)));
+        assert!(!subtable_contains(4, data, GlyphId(11)));
+    }
+
+    #[test]
+    fn malformed_ligatures() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x08, // coverage offset: 8
+            0x00, 0x01, // ligature set count: 1
+            0x00, 0x0E, // ligature set offset [0]: 14
+            // Coverage
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x01, // glyph [0]: 1 (f)
+            // Ligature Set
+            0x00, 0x03, // ligature count: 3
+            0x00, 0x08, // ligature offset [0]: 8
+            0x00, 0x0C, // ligature offset [1]: 12
+            0x00, 0x12, // ligature offset [2]: 18
+            // Ligature [0]
+            0x00, 0x05, // ligature glyph: 5
+            0x00, 0x00, // component count: 0 (invalid)
+            // Ligature [1]
+            0x00, 0x03, // ligature glyph: 3 (fi)
+            0x00, 0x02, // component count: 2
+            0x00, 0x02, // component [1]: 2 (i)
+            // Ligature [2]
+            0x00, 0x06, // ligature glyph: 6
+            0x00, 0x04, // component count: 4
+            0x00, 0x02, // component [1]: 2
+            // Truncated
+        ];
+
+        let subtable = LigatureSubstitution::parse(data).unwrap();
+        let ligatures: Vec<_> = subtable.ligatures(GlyphId(1)).unwrap().collect();
+        assert_eq!(ligatures.len(), 1);
+        assert_eq!(ligatures[0].glyph, GlyphId(3));
+        assert_eq!(ligatures[0].components.len(), 1);
+
+        assert!(Ligature::parse(GlyphId(1), &data[22..]).is_none());
+        assert!(Ligature::parse(GlyphId(1), &data[32..]).is_none());
+    }
+
+    /// Creates a GSUB table with a `liga` feature that uses `lookup_count` lookups.
+    ///
+    /// All lookups point to the same ligature lookup with a single `subtable`.
+    fn ligature_gsub(lookup_count: u16, subtable: &[u8]) -> Vec<u8> {
+        let feature_size = 4 + 2 * lookup_count;
+        let mut data = vec![
+            0x00, 0x01, // major version: 1
+            0x00, 0x00, // minor version: 0
+            0x00, 0x0A, // script list offset: 10
+            0x00, 0x0C, // feature list offset: 12
+        ];
+        data.extend_from_slice(&(20 + feature_size).to_be_bytes()); // lookup list offset
+        data.extend_from_slice(&[
+            // Script List
+            0x00, 0x00, // count: 0
+            // Feature List
+            0x00, 0x01, // count: 1
+            0x6C, 0x69, 0x67, 0x61, // tag [0]: liga
+            0x00, 0x08, // offset [0]: 8
+            // Feature
+            0x00, 0x00, // feature params offset: NULL
+        ]);
+        data.extend_from_slice(&lookup_count.to_be_bytes()); // lookup index count
+        for index in 0..lookup_count {
+            data.extend_from_slice(&index.to_be_bytes()); // lookup index
+        }
+
+        // Lookup List
+        data.extend_from_slice(&lookup_count.to_be_bytes()); // count
+        for _ in 0..lookup_count {
+            data.extend_from_slice(&(2 + 2 * lookup_count).to_be_bytes()); // offset
+        }
+
+        data.extend_from_slice(&[
+            // Lookup
+            0x00, 0x04, // type: 4 (ligature)
+            0x00, 0x00, // flags: 0
+            0x00, 0x01, // subtable count: 1
+            0x00, 0x08, // subtable offset [0]: 8
+        ]);
+        data.extend_from_slice(subtable);
+        data
+    }
+
+    #[test]
+    fn debug_apply_ligature() {
+        let data = &ligature_gsub(1, &[
+            // Ligature Substitution
+            0x00, 0x01, // format: 1
+            0x00, 0x08, // coverage offset: 8
+            0x00, 0x01, // ligature set count: 1
+            0x00, 0x0E, // ligature set offset [0]: 14
+            // Coverage
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x01, // glyph [0]: 1 (f)
+            // Ligature Set
+            0x00, 0x01, // ligature count: 1
+            0x00, 0x04, // ligature offset [0]: 4
+            // Ligature
+            0x00, 0x03, // ligature glyph: 3 (fi)
+            0x00, 0x02, // component count: 2
+            0x00, 0x02, // component [1]: 2 (i)
+        ]);
+
+        let gsub = Gsub::parse(data).unwrap();
+        let liga = Tag::from_bytes(b"liga");
+        let (f, i, fi) = (GlyphId(1), GlyphId(2), GlyphId(3));
+        assert_eq!(gsub.debug_apply(&[f, i], liga), vec![fi]);
+        assert_eq!(gsub.debug_apply(&[i, f, i, f], liga), vec![i, fi, f]);
+        assert_eq!(gsub.debug_apply(&[f], liga), vec![f]);
+        assert_eq!(gsub.debug_apply(&[f, i], Tag::from_bytes(b"smcp")), vec![f, i]);
+    }
+
+    #[test]
+    fn ligature_for() {
+        let data = &ligature_gsub(1, &[
+            // Ligature Substitution
+            0x00, 0x01, // format: 1
+            0x00, 0x08, // coverage offset: 8
+            0x00, 0x01, // ligature set count: 1
+            0x00, 0x0E, // ligature set offset [0]: 14
+            // Coverage
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x01, // glyph [0]: 1 (f)
+            // Ligature Set
+            0x00, 0x02, // ligature count: 2
+            0x00, 0x06, // ligature offset [0]: 6
+            0x00, 0x0E, // ligature offset [1]: 14
+            // Ligature [0]
+            0x00, 0x04, // ligature glyph: 4 (ffi)
+            0x00, 0x03, // component count: 3
+            0x00, 0x01, // component [1]: 1 (f)
+            0x00, 0x02, // component [2]: 2 (i)
+            // Ligature [1]
+            0x00, 0x03, // ligature glyph: 3 (fi)
+            0x00, 0x02, // component count: 2
+            0x00, 0x02, // component [1]: 2 (i)
+        ]);
+
+        let gsub = Gsub::parse(data).unwrap();
+        let liga = Tag::from_bytes(b"liga");
+        let (f, i, fi, ffi) = (GlyphId(1), GlyphId(2), GlyphId(3), GlyphId(4));
+        assert_eq!(gsub.ligature_for(&[f, f, i], liga), Some(ffi));
+        assert_eq!(gsub.ligature_for(&[f, i], liga), Some(fi));
+        assert_eq!(gsub.ligature_for(&[f, f], liga), None);
+        assert_eq!(gsub.ligature_for(&[f, i, i], liga), None);
+        assert_eq!(gsub.ligature_for(&[f], liga), None);
+        assert_eq!(gsub.ligature_for(&[], liga), None);
+        assert_eq!(gsub.ligature_for(&[f, i], Tag::from_bytes(b"dlig")), None);
+    }
+
+    #[test]
+    fn duplicated_ligatures() {
+        let data = &ligature_gsub(2, &[
+            // Ligature Substitution
+            0x00, 0x01, // format: 1
+            0x00, 0x08, // coverage offset: 8
+            0x00, 0x01, // ligature set count: 1
+            0x00, 0x0E, // ligature set offset [0]: 14
+            // Coverage
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x01, // glyph [0]: 1 (f)
+            // Ligature Set
+            0x00, 0x02, // ligature count: 2
+            0x00, 0x06, // ligature offset [0]: 6
+            0x00, 0x06, // ligature offset [1]: 6
+            // Ligature
+            0x00, 0x03, // ligature glyph: 3 (fi)
+            0x00, 0x02, // component count: 2
+            0x00, 0x02, // component [1]: 2 (i)
+        ]);
+
+        // Both lookups contain the same ligature set with a duplicated ligature.
+        let gsub = Gsub::parse(data).unwrap();
+        let ligatures: std::vec::Vec<_> = gsub.ligatures(Tag::from_bytes(b"liga")).collect();
+        assert_eq!(ligatures.len(), 1);
+        assert_eq!(ligatures[0].first_component, GlyphId(1));
+        assert_eq!(ligatures[0].components.into_iter().collect::<std::vec::Vec<_>>(), vec![GlyphId(2)]);
+        assert_eq!(ligatures[0].glyph, GlyphId(3));
+
+        assert_eq!(gsub.ligatures(Tag::from_bytes(b"dlig")).count(), 0);
+    }
+}
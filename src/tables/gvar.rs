@@ -32,7 +32,14 @@ pub struct Table<'a> {
 
 impl<'a> Table<'a> {
     // https://docs.microsoft.com/en-us/typography/opentype/spec/gvar#gvar-header
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    //
+    // `fvar_axis_count` and `number_of_glyphs` are used to reject a table
+    // which header doesn't match the `fvar` and `maxp` tables.
+    pub fn parse(
+        data: &'a [u8],
+        fvar_axis_count: u16,
+        number_of_glyphs: NonZeroU16,
+    ) -> Option<Self> {
         let mut s = Stream::new(data);
         let version: u32 = s.read()?;
         if version != 0x00010000 {
@@ -46,13 +53,26 @@ impl<'a> Table<'a> {
         let flags: u16 = s.read()?;
         let glyph_variation_data_array_offset: Offset32 = s.read()?;
 
+        // 'The number of variation axes for the font. This must be the same number
+        // as axisCount in the 'fvar' table.'
+        if axis_count != fvar_axis_count {
+            return None;
+        }
+
+        // 'The number of glyphs in this font. This must match the number
+        // of glyphs stored elsewhere in the font.'
+        if glyph_count != number_of_glyphs.get() {
+            return None;
+        }
+
         // The axis count cannot be zero.
         let axis_count = NonZeroU16::new(axis_count)?;
 
-        let shared_tuple_records = {
-            let mut sub_s = Stream::new_at(data, shared_tuples_offset.to_usize())?;
-            sub_s.read_array16::<F2DOT14>(shared_tuple_count.checked_mul(axis_count.get())?)?
-        };
+        let shared_tuple_records = Stream::read_array16_at::<F2DOT14>(
+            data,
+            shared_tuples_offset.to_usize(),
+            shared_tuple_count.checked_mul(axis_count.get())?,
+        )?;
 
         let glyphs_variation_data = data.get(glyph_variation_data_array_offset.to_usize()..)?;
         let offsets = {
@@ -500,8 +520,7 @@ fn parse_tuple_variation_header(
     } else {
         // Use shared tuples.
         let start = tuple_index.checked_mul(axis_count)?;
-        let end = start.checked_add(axis_count)?;
-        shared_tuple_records.slice(start..end)?
+        shared_tuple_records.subarray(start, axis_count)?
     };
 
     let (start_tuple, end_tuple) = if has_intermediate_region {
@@ -1566,3 +1585,36 @@ fn infer_delta(
         (1.0 - d) * prev_delta + d * next_delta
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = &[
+        0x00, 0x01, 0x00, 0x00, // version: 1.0
+        0x00, 0x01, // axis count: 1
+        0x00, 0x00, // shared tuple count: 0
+        0x00, 0x00, 0x00, 0x18, // shared tuples offset: 24
+        0x00, 0x01, // glyph count: 1
+        0x00, 0x00, // flags: short offsets
+        0x00, 0x00, 0x00, 0x18, // glyph variation data array offset: 24
+        0x00, 0x00, // offset [0]: 0
+        0x00, 0x00, // offset [1]: 0
+    ];
+
+    #[test]
+    fn valid_header() {
+        assert!(Table::parse(DATA, 1, NonZeroU16::new(1).unwrap()).is_some());
+    }
+
+    #[test]
+    fn axis_count_mismatch() {
+        assert!(Table::parse(DATA, 2, NonZeroU16::new(1).unwrap()).is_none());
+    }
+
+    #[test]
+    fn glyph_count_mismatch() {
+        assert!(Table::parse(DATA, 1, NonZeroU16::new(2).unwrap()).is_none());
+    }
+}
@@ -1,12 +1,15 @@
 // https://docs.microsoft.com/en-us/typography/opentype/spec/head
 
 use crate::Rect;
-use crate::parser::Stream;
+use crate::parser::{Stream, LongDateTime};
 
 
 const TABLE_SIZE: usize = 54;
 const UNITS_PER_EM_OFFSET: usize = 18;
+const CREATED_OFFSET: usize = 20;
+const MODIFIED_OFFSET: usize = 28;
 const BBOX_OFFSET: usize = 36;
+const MAC_STYLE_OFFSET: usize = 44;
 const INDEX_TO_LOC_FORMAT_OFFSET: usize = 50;
 
 
@@ -35,6 +38,16 @@ pub fn units_per_em(data: &[u8]) -> Option<u16> {
     }
 }
 
+#[inline]
+pub fn created(data: &[u8]) -> Option<LongDateTime> {
+    Stream::read_at(data, CREATED_OFFSET)
+}
+
+#[inline]
+pub fn modified(data: &[u8]) -> Option<LongDateTime> {
+    Stream::read_at(data, MODIFIED_OFFSET)
+}
+
 #[inline]
 pub fn global_bbox(data: &[u8]) -> Option<Rect> {
     let mut s = Stream::new_at(data, BBOX_OFFSET)?;
@@ -46,6 +59,28 @@ pub fn global_bbox(data: &[u8]) -> Option<Rect> {
     })
 }
 
+#[inline]
+pub fn is_regular(data: &[u8]) -> bool {
+    // `macStyle` has no Regular flag, so we check that the table is present
+    // and that neither Bold nor Italic is set.
+    data.len() == TABLE_SIZE && !is_bold(data) && !is_italic(data)
+}
+
+#[inline]
+pub fn is_bold(data: &[u8]) -> bool {
+    mac_style(data) & (1 << 0) != 0
+}
+
+#[inline]
+pub fn is_italic(data: &[u8]) -> bool {
+    mac_style(data) & (1 << 1) != 0
+}
+
+#[inline]
+fn mac_style(data: &[u8]) -> u16 {
+    Stream::read_at(data, MAC_STYLE_OFFSET).unwrap_or(0)
+}
+
 #[inline]
 pub(crate) fn index_to_loc_format(data: &[u8]) -> Option<IndexToLocationFormat> {
     let format: i16 = Stream::read_at(data, INDEX_TO_LOC_FORMAT_OFFSET)?;
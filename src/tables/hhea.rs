@@ -9,9 +9,29 @@ const TABLE_SIZE: usize = 36;
 const ASCENDER_OFFSET: usize = 4;
 const DESCENDER_OFFSET: usize = 6;
 const LINE_GAP_OFFSET: usize = 8;
+const CARET_SLOPE_RISE_OFFSET: usize = 18;
 const NUMBER_OF_H_METRICS_OFFSET: usize = 34;
 
 
+/// A caret slope.
+///
+/// An upright caret has `rise` set to 1 and `run` set to 0.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CaretSlope {
+    /// Slope rise.
+    pub rise: i16,
+
+    /// Slope run.
+    pub run: i16,
+
+    /// An amount by which a slanted highlight on a glyph should be shifted.
+    ///
+    /// Zero for non-slanted fonts.
+    pub offset: i16,
+}
+
+
 #[inline]
 pub fn parse(data: &[u8]) -> Option<&[u8]> {
     if data.len() == TABLE_SIZE {
@@ -36,7 +56,47 @@ pub fn line_gap(data: &[u8]) -> i16 {
     Stream::read_at::<i16>(data, LINE_GAP_OFFSET).unwrap_or(0)
 }
 
+#[inline]
+pub fn caret_slope(data: &[u8]) -> CaretSlope {
+    let mut s = Stream::new_at(data, CARET_SLOPE_RISE_OFFSET).unwrap_or_default();
+    CaretSlope {
+        rise: s.read::<i16>().unwrap_or(1),
+        run: s.read::<i16>().unwrap_or(0),
+        offset: s.read::<i16>().unwrap_or(0),
+    }
+}
+
 #[inline]
 pub fn number_of_h_metrics(data: &[u8]) -> Option<NonZeroU16> {
     Stream::read_at::<u16>(data, NUMBER_OF_H_METRICS_OFFSET).and_then(NonZeroU16::new)
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn italic_caret_slope() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x03, 0x84, // ascender: 900
+            0xFF, 0x06, // descender: -250
+            0x00, 0x00, // line gap: 0
+            0x04, 0xB0, // advance width max: 1200
+            0xFF, 0x9C, // min left side bearing: -100
+            0xFF, 0x9C, // min right side bearing: -100
+            0x04, 0xB0, // x max extent: 1200
+            0x03, 0xE8, // caret slope rise: 1000
+            0x00, 0xD4, // caret slope run: 212
+            0xFF, 0xEC, // caret offset: -20
+            0x00, 0x00, 0x00, 0x00, // reserved
+            0x00, 0x00, 0x00, 0x00, // reserved
+            0x00, 0x00, // metric data format: 0
+            0x00, 0x01, // number of long horizontal metrics: 1
+        ];
+
+        let data = parse(data).unwrap();
+        assert_eq!(caret_slope(data), CaretSlope { rise: 1000, run: 212, offset: -20 });
+    }
+}
@@ -142,6 +142,33 @@ mod tests {
         assert_eq!(table.side_bearing(GlyphId(1)), Some(3));
     }
 
+    #[test]
+    fn trailing_side_bearings() {
+        let data = &[
+            0x00, 0x01, // advance width [0]: 1
+            0x00, 0x02, // side bearing [0]: 2
+
+            0x00, 0x03, // advance width [1]: 3
+            0x00, 0x04, // side bearing [1]: 4
+
+            0x00, 0x05, // side bearing [2]: 5
+            0xFF, 0xFA, // side bearing [3]: -6
+            0x00, 0x07, // side bearing [4]: 7
+        ];
+
+        let table = Table::parse(data, nzu16!(2), nzu16!(5)).unwrap();
+        assert_eq!(table.side_bearing(GlyphId(1)), Some(4));
+
+        // The last advance is repeated, while side bearings are still different.
+        for (id, bearing) in [(2, 5), (3, -6), (4, 7)].iter() {
+            assert_eq!(table.advance(GlyphId(*id)), Some(3));
+            assert_eq!(table.side_bearing(GlyphId(*id)), Some(*bearing));
+        }
+
+        assert_eq!(table.advance(GlyphId(5)), None);
+        assert_eq!(table.side_bearing(GlyphId(5)), None);
+    }
+
     #[test]
     fn less_metrics_than_glyphs() {
         let data = &[
@@ -1,10 +1,8 @@
 // https://docs.microsoft.com/en-us/typography/opentype/spec/hvar
 
-use core::convert::TryFrom;
-
 use crate::{GlyphId, NormalizedCoordinate};
 use crate::parser::{Stream, Offset, Offset32};
-use crate::var_store::ItemVariationStore;
+use crate::var_store::{ItemVariationStore, DeltaSetIndexMap, RegionScalars};
 
 #[derive(Clone, Copy)]
 pub struct Table<'a> {
@@ -34,72 +32,50 @@ impl<'a> Table<'a> {
             lsb_mapping_offset: s.read::<Option<Offset32>>()?,
         })
     }
-}
-
 
-pub struct DeltaSetIndexMap<'a> {
-    data: &'a [u8],
-}
-
-impl<'a> DeltaSetIndexMap<'a> {
     #[inline]
-    pub fn new(data: &'a [u8]) -> Self {
-        DeltaSetIndexMap { data }
+    pub fn region_scalars(&self, coordinates: &[NormalizedCoordinate]) -> RegionScalars {
+        RegionScalars::new(&self.variation_store.regions, coordinates)
     }
 
     #[inline]
-    pub fn map(&self, glyph_id: GlyphId) -> Option<(u16, u16)> {
-        let mut idx = glyph_id.0;
-
-        let mut s = Stream::new(self.data);
-        let entry_format: u16 = s.read()?;
-        let map_count: u16 = s.read()?;
-
-        if map_count == 0 {
-            return None;
+    fn parse_delta(
+        &self,
+        outer_index: u16,
+        inner_index: u16,
+        coordinates: &[NormalizedCoordinate],
+        scalars: Option<&RegionScalars>,
+    ) -> Option<f32> {
+        match scalars {
+            Some(scalars) => {
+                self.variation_store.parse_delta_cached(outer_index, inner_index, coordinates, scalars)
+            }
+            None => self.variation_store.parse_delta(outer_index, inner_index, coordinates),
         }
-
-        // 'If a given glyph ID is greater than mapCount-1, then the last entry is used.'
-        if idx >= map_count {
-            idx = map_count - 1;
-        }
-
-        let entry_size = ((entry_format >> 4) & 3) + 1;
-        let inner_index_bit_count = u32::from((entry_format & 0xF) + 1);
-
-        s.advance(usize::from(entry_size) * usize::from(idx));
-
-        let mut n = 0u32;
-        for b in s.read_bytes(usize::from(entry_size))? {
-            n = (n << 8) + u32::from(*b);
-        }
-
-        let outer_index = n >> inner_index_bit_count;
-        let inner_index = n & ((1 << inner_index_bit_count) - 1);
-        Some((
-            u16::try_from(outer_index).ok()?,
-            u16::try_from(inner_index).ok()?
-        ))
     }
 }
 
+
 #[inline]
 pub(crate) fn glyph_advance_offset(
     table: Table,
     glyph_id: GlyphId,
     coordinates: &[NormalizedCoordinate],
+    scalars: Option<&RegionScalars>,
 ) -> Option<f32> {
-    let (outer_idx, inner_idx) = if let Some(offset) = table.advance_width_mapping_offset {
-        DeltaSetIndexMap::new(table.data.get(offset.to_usize()..)?).map(glyph_id)?
+    let map = if let Some(offset) = table.advance_width_mapping_offset {
+        DeltaSetIndexMap::new(table.data.get(offset.to_usize()..)?)
     } else {
         // 'If there is no delta-set index mapping table for advance widths,
         // then glyph IDs implicitly provide the indices:
         // for a given glyph ID, the delta-set outer-level index is zero,
         // and the glyph ID is the delta-set inner-level index.'
-        (0, glyph_id.0)
+        DeltaSetIndexMap::default()
     };
 
-    table.variation_store.parse_delta(outer_idx, inner_idx, coordinates)
+    let (outer_idx, inner_idx) = map.map(u32::from(glyph_id.0))?;
+
+    table.parse_delta(outer_idx, inner_idx, coordinates, scalars)
 }
 
 #[inline]
@@ -107,8 +83,9 @@ pub(crate) fn glyph_side_bearing_offset(
     table: Table,
     glyph_id: GlyphId,
     coordinates: &[NormalizedCoordinate],
+    scalars: Option<&RegionScalars>,
 ) -> Option<f32> {
     let set_data = table.data.get(table.lsb_mapping_offset?.to_usize()..)?;
-    let (outer_idx, inner_idx) = DeltaSetIndexMap::new(set_data).map(glyph_id)?;
-    table.variation_store.parse_delta(outer_idx, inner_idx, coordinates)
+    let (outer_idx, inner_idx) = DeltaSetIndexMap::new(set_data).map(u32::from(glyph_id.0))?;
+    table.parse_delta(outer_idx, inner_idx, coordinates, scalars)
 }
@@ -135,6 +135,18 @@ impl<'a> Subtable<'a> {
         self.format == 1
     }
 
+    /// Returns the number of kerning pairs.
+    ///
+    /// Returns `None` for non-format 0 subtables, since they don't store pairs explicitly.
+    #[inline]
+    pub fn pairs_count(&self) -> Option<u16> {
+        if self.format == 0 {
+            Stream::read_at::<u16>(self.data, 0)
+        } else {
+            None
+        }
+    }
+
     /// Returns kerning for a pair of glyphs.
     ///
     /// Returns `None` in case of state machine based subtable.
@@ -186,6 +198,16 @@ pub struct Subtables<'a> {
     stream: Stream<'a>,
 }
 
+impl<'a> Subtables<'a> {
+    /// Returns the number of subtables declared in the table header.
+    ///
+    /// Unknown and malformed subtables are still counted.
+    #[inline]
+    pub fn subtable_count(&self) -> u32 {
+        self.number_of_tables
+    }
+}
+
 impl<'a> Iterator for Subtables<'a> {
     type Item = Subtable<'a>;
 
@@ -572,3 +594,46 @@ pub mod state_machine {
         }
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format0_pairs_count() {
+        let data = &[
+            0x00, 0x00, // version: 0
+            0x00, 0x01, // number of tables: 1
+            // Subtable
+            0x00, 0x00, // version: 0
+            0x00, 0x20, // length: 32
+            0x00, // format: 0
+            0x01, // coverage: horizontal
+            0x00, 0x03, // number of pairs: 3
+            0x00, 0x0C, // search range: 12
+            0x00, 0x01, // entry selector: 1
+            0x00, 0x06, // range shift: 6
+            // Pair [0]
+            0x00, 0x01, // left: 1
+            0x00, 0x02, // right: 2
+            0xFF, 0xF6, // value: -10
+            // Pair [1]
+            0x00, 0x01, // left: 1
+            0x00, 0x03, // right: 3
+            0xFF, 0xEC, // value: -20
+            // Pair [2]
+            0x00, 0x04, // left: 4
+            0x00, 0x02, // right: 2
+            0x00, 0x0A, // value: 10
+        ];
+
+        let subtables = parse(data).unwrap();
+        assert_eq!(subtables.subtable_count(), 1);
+
+        let subtable = subtables.into_iter().next().unwrap();
+        assert_eq!(subtable.pairs_count(), Some(3));
+        assert_eq!(subtable.glyphs_kerning(GlyphId(1), GlyphId(3)), Some(-20));
+        assert_eq!(subtable.glyphs_kerning(GlyphId(2), GlyphId(1)), None);
+    }
+}
@@ -21,12 +21,30 @@ impl<'a> Table<'a> {
         // The number of ranges is `maxp.numGlyphs + 1`.
         //
         // Check for overflow first.
-        let total = if number_of_glyphs.get() == core::u16::MAX {
+        let mut total = if number_of_glyphs.get() == core::u16::MAX {
             number_of_glyphs.get()
         } else {
             number_of_glyphs.get() + 1
         };
 
+        // `maxp.numGlyphs` and the number of `loca` entries may disagree.
+        // When `loca` has only `n + 1` entries, only the first `n` glyphs can be accessed,
+        // so the effective number of glyphs is `min(maxp.numGlyphs, n)`.
+        // Extra `loca` entries are ignored.
+        let entry_size = match format {
+            IndexToLocationFormat::Short => 2,
+            IndexToLocationFormat::Long => 4,
+        };
+        let available = data.len() / entry_size;
+        if available < usize::from(total) {
+            // At least two entries are required to describe a single glyph.
+            if available < 2 {
+                return None;
+            }
+
+            total = available as u16;
+        }
+
         let mut s = Stream::new(data);
         match format {
             IndexToLocationFormat::Short => {
@@ -77,3 +95,52 @@ impl<'a> Table<'a> {
         }
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! nzu16 {
+        ($n:expr) => { NonZeroU16::new($n).unwrap() };
+    }
+
+    #[test]
+    fn less_entries_than_glyphs() {
+        let data = &[
+            0x00, 0x00, // offset [0]: 0
+            0x00, 0x05, // offset [1]: 10
+            0x00, 0x0A, // offset [2]: 20
+        ];
+
+        // `maxp` claims 4 glyphs, but `loca` describes only 2.
+        let table = Table::parse(data, nzu16!(4), IndexToLocationFormat::Short).unwrap();
+        assert_eq!(table.glyph_range(GlyphId(0)), Some(0..10));
+        assert_eq!(table.glyph_range(GlyphId(1)), Some(10..20));
+        assert_eq!(table.glyph_range(GlyphId(2)), None);
+        assert_eq!(table.glyph_range(GlyphId(3)), None);
+    }
+
+    #[test]
+    fn more_entries_than_glyphs() {
+        let data = &[
+            0x00, 0x00, 0x00, 0x00, // offset [0]: 0
+            0x00, 0x00, 0x00, 0x0A, // offset [1]: 10
+            0x00, 0x00, 0x00, 0x14, // offset [2]: 20
+        ];
+
+        // `maxp` claims 1 glyph, so the last entry is ignored.
+        let table = Table::parse(data, nzu16!(1), IndexToLocationFormat::Long).unwrap();
+        assert_eq!(table.glyph_range(GlyphId(0)), Some(0..10));
+        assert_eq!(table.glyph_range(GlyphId(1)), None);
+    }
+
+    #[test]
+    fn single_entry() {
+        let data = &[
+            0x00, 0x00, // offset [0]: 0
+        ];
+
+        assert!(Table::parse(data, nzu16!(1), IndexToLocationFormat::Short).is_none());
+    }
+}
@@ -3,8 +3,13 @@ pub mod cbdt;
 pub mod cblc;
 mod cff;
 pub mod cmap;
+pub mod colr;
+pub mod cpal;
+pub mod ebsc;
 pub mod fvar;
 pub mod gdef;
+pub mod gpos;
+pub mod gsub;
 pub mod glyf;
 pub mod gvar;
 pub mod head;
@@ -19,6 +24,7 @@ pub mod name;
 pub mod os2;
 pub mod post;
 pub mod sbix;
+pub mod stat;
 pub mod svg;
 pub mod vhea;
 pub mod vorg;
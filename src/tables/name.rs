@@ -4,8 +4,12 @@
 use std::vec::Vec;
 #[cfg(feature = "std")]
 use std::string::String;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "alloc"))]
 use crate::parser::LazyArray16;
 
 use crate::parser::{Stream, FromData};
@@ -91,7 +95,7 @@ fn is_unicode_encoding(platform_id: PlatformId, encoding_id: u16) -> bool {
 
 
 #[derive(Clone, Copy)]
-struct NameRecord {
+struct RawNameRecord {
     platform_id: PlatformId,
     encoding_id: u16,
     language_id: u16,
@@ -100,13 +104,13 @@ struct NameRecord {
     offset: u16,
 }
 
-impl FromData for NameRecord {
+impl FromData for RawNameRecord {
     const SIZE: usize = 12;
 
     #[inline]
     fn parse(data: &[u8]) -> Option<Self> {
         let mut s = Stream::new(data);
-        Some(NameRecord {
+        Some(RawNameRecord {
             platform_id: s.read::<PlatformId>()?,
             encoding_id: s.read::<u16>()?,
             language_id: s.read::<u16>()?,
@@ -121,7 +125,7 @@ impl FromData for NameRecord {
 /// A [Name Record](https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-records).
 #[derive(Clone, Copy)]
 pub struct Name<'a> {
-    data: NameRecord,
+    data: RawNameRecord,
     strings: &'a [u8],
 }
 
@@ -166,14 +170,10 @@ impl<'a> Name<'a> {
     /// - Unicode Platform ID
     /// - Windows Platform ID + Symbol
     /// - Windows Platform ID + Unicode BMP
-    #[cfg(feature = "std")]
-    #[inline(never)]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[inline]
     pub fn to_string(&self) -> Option<String> {
-        if self.is_unicode() {
-            self.name_from_utf16_be()
-        } else {
-            None
-        }
+        self.record().to_string()
     }
 
     /// Checks that the current Name data has a Unicode encoding.
@@ -182,19 +182,20 @@ impl<'a> Name<'a> {
         is_unicode_encoding(self.platform_id(), self.encoding_id())
     }
 
-    #[cfg(feature = "std")]
-    #[inline(never)]
-    fn name_from_utf16_be(&self) -> Option<String> {
-        let mut name: Vec<u16> = Vec::new();
-        for c in LazyArray16::<u16>::new(self.name()) {
-            name.push(c);
+    /// Returns all fields of the Name Record at once.
+    #[inline]
+    pub fn record(&self) -> NameRecord<'a> {
+        NameRecord {
+            platform_id: self.platform_id(),
+            encoding_id: self.encoding_id(),
+            language_id: self.language_id(),
+            name_id: self.name_id(),
+            raw_bytes: self.name(),
         }
-
-        String::from_utf16(&name).ok()
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a> core::fmt::Debug for Name<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         // TODO: https://github.com/rust-lang/rust/issues/50264
@@ -211,7 +212,7 @@ impl<'a> core::fmt::Debug for Name<'a> {
     }
 }
 
-#[cfg(not(feature = "std"))]
+#[cfg(not(any(feature = "std", feature = "alloc")))]
 impl<'a> core::fmt::Debug for Name<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.debug_struct("Name")
@@ -225,6 +226,87 @@ impl<'a> core::fmt::Debug for Name<'a> {
 }
 
 
+/// A [Name Record](https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-records)
+/// with all its fields.
+///
+/// Unlike `Name`, fields are public, but the name is still stored as raw bytes
+/// and decoded only on demand.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NameRecord<'a> {
+    /// The platform ID.
+    pub platform_id: PlatformId,
+
+    /// The platform-specific encoding ID.
+    pub encoding_id: u16,
+
+    /// The language ID.
+    pub language_id: u16,
+
+    /// The [Name ID](https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-ids).
+    pub name_id: u16,
+
+    /// The name's data as bytes.
+    ///
+    /// Can be empty.
+    pub raw_bytes: &'a [u8],
+}
+
+impl<'a> NameRecord<'a> {
+    /// Checks that the name has a Unicode encoding.
+    #[inline]
+    pub fn is_unicode(&self) -> bool {
+        is_unicode_encoding(self.platform_id, self.encoding_id)
+    }
+
+    /// Returns the name as a UTF-8 string.
+    ///
+    /// See `Name::to_string` for details.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[inline(never)]
+    pub fn to_string(&self) -> Option<String> {
+        if !self.is_unicode() {
+            return None;
+        }
+
+        let mut name: Vec<u16> = Vec::new();
+        for c in LazyArray16::<u16>::new(self.raw_bytes) {
+            name.push(c);
+        }
+
+        String::from_utf16(&name).ok()
+    }
+}
+
+
+/// An iterator over font's name records.
+///
+/// Same as `Names`, but yields `NameRecord`.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct NameRecords<'a>(Names<'a>);
+
+impl<'a> NameRecords<'a> {
+    #[inline]
+    pub(crate) fn new(names: Names<'a>) -> Self {
+        NameRecords(names)
+    }
+}
+
+impl<'a> Iterator for NameRecords<'a> {
+    type Item = NameRecord<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|name| name.record())
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.0.count()
+    }
+}
+
+
 /// An iterator over font's names.
 #[derive(Clone, Copy, Default)]
 #[allow(missing_debug_implementations)]
@@ -254,7 +336,7 @@ impl<'a> Iterator for Names<'a> {
             let index = usize::from(self.index);
             self.index += 1;
             Some(Name {
-                data: Stream::read_at::<NameRecord>(self.names, NameRecord::SIZE * index)?,
+                data: Stream::read_at::<RawNameRecord>(self.names, RawNameRecord::SIZE * index)?,
                 strings: self.storage,
             })
         } else {
@@ -279,14 +361,14 @@ pub(crate) fn parse(data: &[u8]) -> Option<Names> {
     s.skip::<u16>(); // offset
 
     if format == 0 {
-        let names_data = s.read_bytes(NameRecord::SIZE * usize::from(count))?;
+        let names_data = s.read_bytes(RawNameRecord::SIZE * usize::from(count))?;
         Some(Names::new(names_data, s.tail()?, count))
     } else if format == 1 {
         let lang_tag_count: u16 = s.read()?;
         let lang_tag_len = lang_tag_count.checked_mul(LANG_TAG_RECORD_SIZE)?;
 
         s.advance(usize::from(lang_tag_len)); // langTagRecords
-        let names_data = s.read_bytes(NameRecord::SIZE * usize::from(count))?;
+        let names_data = s.read_bytes(RawNameRecord::SIZE * usize::from(count))?;
         Some(Names::new(names_data, s.tail()?, count))
     } else {
         None
@@ -296,7 +296,7 @@ impl<'a> Table<'a> {
             return None;
         }
 
-        let italic_angle = Stream::read_at::<Fixed>(data, ITALIC_ANGLE_OFFSET)?.0;
+        let italic_angle = Stream::read_at::<Fixed>(data, ITALIC_ANGLE_OFFSET)?.to_f32();
 
         let underline = LineMetrics {
             position: Stream::read_at::<i16>(data, UNDERLINE_POSITION_OFFSET)?,
@@ -377,3 +377,37 @@ impl<'a> Table<'a> {
         }
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_range_name_index() {
+        let data = &[
+            0x00, 0x02, 0x00, 0x00, // version: 2.0
+            0x00, 0x00, 0x00, 0x00, // italic angle: 0
+            0xFF, 0x9C, // underline position: -100
+            0x00, 0x32, // underline thickness: 50
+            0x00, 0x00, 0x00, 0x00, // is fixed pitch: false
+            0x00, 0x00, 0x00, 0x00, // min memory type 42: 0
+            0x00, 0x00, 0x00, 0x00, // max memory type 42: 0
+            0x00, 0x00, 0x00, 0x00, // min memory type 1: 0
+            0x00, 0x00, 0x00, 0x00, // max memory type 1: 0
+            0x00, 0x03, // number of glyphs: 3
+            0x00, 0x00, // glyph name index [0]: 0
+            0x01, 0x02, // glyph name index [1]: 258
+            0x01, 0x04, // glyph name index [2]: 260
+            // Names
+            0x03, // length: 3
+            0x66, 0x6F, 0x6F, // foo
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.glyph_name(GlyphId(0)), Some(".notdef"));
+        assert_eq!(table.glyph_name(GlyphId(1)), Some("foo"));
+        assert_eq!(table.glyph_name(GlyphId(2)), None);
+        assert_eq!(table.glyph_name(GlyphId(3)), None);
+    }
+}
@@ -0,0 +1,361 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/stat
+
+use crate::Tag;
+use crate::name::{Name, Names};
+use crate::parser::{Stream, FromData, Fixed, Offset16, Offset32, Offset, Offsets16, OffsetsIter16};
+use crate::parser::{LazyArray16, LazyArrayIter16};
+
+
+#[derive(Clone, Copy)]
+struct AxisValueRecord {
+    axis_index: u16,
+    value: Fixed,
+}
+
+impl FromData for AxisValueRecord {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(AxisValueRecord {
+            axis_index: s.read::<u16>()?,
+            value: s.read::<Fixed>()?,
+        })
+    }
+}
+
+
+/// A list of design axes.
+///
+/// Unlike most records, `AxisRecord` has a variable size, so we cannot use `LazyArray16`.
+#[derive(Clone, Copy, Default)]
+struct DesignAxes<'a> {
+    data: &'a [u8],
+    record_size: u16,
+    count: u16,
+}
+
+impl<'a> DesignAxes<'a> {
+    fn tag(&self, index: u16) -> Option<Tag> {
+        if index < self.count {
+            Stream::read_at(self.data, usize::from(index) * usize::from(self.record_size))
+        } else {
+            None
+        }
+    }
+
+    fn ordering(&self, tag: Tag) -> Option<u16> {
+        (0..self.count).find_map(|index| {
+            let mut s = Stream::new_at(self.data,
+                                       usize::from(index) * usize::from(self.record_size))?;
+            let axis_tag: Tag = s.read()?;
+            s.skip::<u16>(); // axisNameID
+            let ordering: u16 = s.read()?;
+            if axis_tag == tag { Some(ordering) } else { None }
+        })
+    }
+}
+
+
+/// A [style attributes](https://docs.microsoft.com/en-us/typography/opentype/spec/stat)
+/// axis value.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct AxisValue<'a> {
+    /// Axis value flags.
+    pub flags: u16,
+
+    /// A value name in the `name` table.
+    pub value_name_id: u16,
+
+    /// A value name resolved from the `name` table.
+    ///
+    /// This is the first Unicode name with `value_name_id`.
+    /// Use `Name::to_string` to get the actual string.
+    pub name: Option<Name<'a>>,
+
+    axes: DesignAxes<'a>,
+    record: Option<AxisValueRecord>,
+    records: LazyArray16<'a, AxisValueRecord>,
+}
+
+impl<'a> AxisValue<'a> {
+    fn parse(data: &'a [u8], axes: DesignAxes<'a>) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format: u16 = s.read()?;
+        match format {
+            1..=3 => {
+                let axis_index: u16 = s.read()?;
+                let flags: u16 = s.read()?;
+                let value_name_id: u16 = s.read()?;
+                // A value for format 1 and 3 and a nominal value for format 2.
+                let value: Fixed = s.read()?;
+                Some(AxisValue {
+                    flags,
+                    value_name_id,
+                    name: None,
+                    axes,
+                    record: Some(AxisValueRecord { axis_index, value }),
+                    records: LazyArray16::default(),
+                })
+            }
+            4 => {
+                let count: u16 = s.read()?;
+                let flags: u16 = s.read()?;
+                let value_name_id: u16 = s.read()?;
+                let records = s.read_array16::<AxisValueRecord>(count)?;
+                Some(AxisValue {
+                    flags,
+                    value_name_id,
+                    name: None,
+                    axes,
+                    record: None,
+                    records,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks that a value is older than the font's default value and
+    /// should not be presented to the user.
+    #[inline]
+    pub fn is_older_sibling_font_attribute(&self) -> bool {
+        self.flags & 0x0001 != 0
+    }
+
+    /// Checks that a value name can be omitted when composing a style name.
+    #[inline]
+    pub fn is_elidable(&self) -> bool {
+        self.flags & 0x0002 != 0
+    }
+
+    /// Returns an iterator over axis tag and value pairs.
+    ///
+    /// Yields a single pair for format 1, 2 and 3 values and
+    /// a pair per axis for format 4 values.
+    /// For format 2 values, the nominal value is used.
+    #[inline]
+    pub fn values(&self) -> AxisValueValues<'a> {
+        AxisValueValues {
+            axes: self.axes,
+            record: self.record,
+            records: self.records.into_iter(),
+        }
+    }
+}
+
+
+/// An iterator over axis tag and value pairs of an `AxisValue`.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct AxisValueValues<'a> {
+    axes: DesignAxes<'a>,
+    record: Option<AxisValueRecord>,
+    records: LazyArrayIter16<'a, AxisValueRecord>,
+}
+
+impl<'a> Iterator for AxisValueValues<'a> {
+    type Item = (Tag, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = match self.record.take() {
+            Some(record) => record,
+            None => self.records.next()?,
+        };
+
+        Some((self.axes.tag(record.axis_index)?, record.value.to_f32()))
+    }
+}
+
+
+/// An iterator over [style attributes](https://docs.microsoft.com/en-us/typography/opentype/spec/stat)
+/// axis values.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct AxisValues<'a> {
+    axes: DesignAxes<'a>,
+    offsets: OffsetsIter16<'a, Offset16>,
+    names: Names<'a>,
+}
+
+impl<'a> Iterator for AxisValues<'a> {
+    type Item = AxisValue<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Skip malformed and unknown values.
+            if let Some(mut value) = AxisValue::parse(self.offsets.next()?, self.axes) {
+                let id = value.value_name_id;
+                let mut names = self.names;
+                value.name = names.find(|name| name.name_id() == id && name.is_unicode());
+                return Some(value);
+            }
+        }
+    }
+}
+
+
+#[derive(Clone, Copy)]
+pub(crate) struct Table<'a> {
+    axes: DesignAxes<'a>,
+    offsets: Offsets16<'a, Offset16>,
+}
+
+impl<'a> Table<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        // Design axis record without any additional data.
+        const AXIS_RECORD_SIZE: u16 = 8;
+
+        let mut s = Stream::new(data);
+        let major_version: u16 = s.read()?;
+        s.skip::<u16>(); // minor version
+        if major_version != 1 {
+            return None;
+        }
+
+        let design_axis_size: u16 = s.read()?;
+        let design_axis_count: u16 = s.read()?;
+        let design_axes_offset: Offset32 = s.read()?;
+        let axis_value_count: u16 = s.read()?;
+        let axis_value_offsets_offset: Offset32 = s.read()?;
+
+        if design_axis_size < AXIS_RECORD_SIZE {
+            return None;
+        }
+
+        let axes = if design_axis_count != 0 {
+            let len = usize::from(design_axis_size) * usize::from(design_axis_count);
+            let start = design_axes_offset.to_usize();
+            DesignAxes {
+                data: data.get(start..start + len)?,
+                record_size: design_axis_size,
+                count: design_axis_count,
+            }
+        } else {
+            DesignAxes::default()
+        };
+
+        // Axis value offsets are from the start of the offsets array.
+        let offsets_data = data.get(axis_value_offsets_offset.to_usize()..)?;
+        let mut s = Stream::new(offsets_data);
+        let offsets = s.read_offsets16(axis_value_count, offsets_data)?;
+
+        Some(Table { axes, offsets })
+    }
+
+    /// Returns an axis ordering from the design axis record with the specified tag.
+    #[inline]
+    pub fn axis_ordering(&self, tag: Tag) -> Option<u16> {
+        self.axes.ordering(tag)
+    }
+
+    pub fn axis_values(&self, names: Names<'a>) -> AxisValues<'a> {
+        AxisValues {
+            axes: self.axes,
+            offsets: self.offsets.into_iter(),
+            names,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAME_DATA: &[u8] = &[
+        0x00, 0x00, // format: 0
+        0x00, 0x02, // count: 2
+        0x00, 0x1E, // strings offset: 30
+        // Name Record [0]
+        0x00, 0x03, // platform ID: Windows
+        0x00, 0x01, // encoding ID: Unicode BMP
+        0x04, 0x09, // language ID: English
+        0x01, 0x00, // name ID: 256
+        0x00, 0x08, // length: 8
+        0x00, 0x00, // offset: 0
+        // Name Record [1]
+        0x00, 0x03, // platform ID: Windows
+        0x00, 0x01, // encoding ID: Unicode BMP
+        0x04, 0x09, // language ID: English
+        0x01, 0x01, // name ID: 257
+        0x00, 0x0C, // length: 12
+        0x00, 0x08, // offset: 8
+        // Strings
+        0x00, 0x42, 0x00, 0x6F, 0x00, 0x6C, 0x00, 0x64, // Bold
+        0x00, 0x49, 0x00, 0x74, 0x00, 0x61, 0x00, 0x6C, 0x00, 0x69, 0x00, 0x63, // Italic
+    ];
+
+    const STAT_DATA: &[u8] = &[
+        0x00, 0x01, // major version: 1
+        0x00, 0x01, // minor version: 1
+        0x00, 0x08, // design axis size: 8
+        0x00, 0x02, // design axis count: 2
+        0x00, 0x00, 0x00, 0x14, // design axes offset: 20
+        0x00, 0x03, // axis value count: 3
+        0x00, 0x00, 0x00, 0x24, // axis value offsets offset: 36
+        0x00, 0x02, // elided fallback name ID: 2
+        // Design Axis [0]
+        0x77, 0x67, 0x68, 0x74, // tag: wght
+        0x01, 0x02, // axis name ID: 258
+        0x00, 0x00, // axis ordering: 0
+        // Design Axis [1]
+        0x69, 0x74, 0x61, 0x6C, // tag: ital
+        0x01, 0x03, // axis name ID: 259
+        0x00, 0x01, // axis ordering: 1
+        // Axis Value Offsets
+        0x00, 0x06, // offset [0]: 6
+        0x00, 0x12, // offset [1]: 18
+        0x00, 0x1E, // offset [2]: 30
+        // Axis Value [0]
+        0x00, 0x01, // format: 1
+        0x00, 0x00, // axis index: 0
+        0x00, 0x00, // flags: 0
+        0x01, 0x00, // value name ID: 256
+        0x02, 0xBC, 0x00, 0x00, // value: 700
+        // Axis Value [1]
+        0x00, 0x03, // format: 3
+        0x00, 0x01, // axis index: 1
+        0x00, 0x02, // flags: elidable
+        0x01, 0x01, // value name ID: 257
+        0x00, 0x01, 0x00, 0x00, // value: 1
+        // Axis Value [2]
+        0x00, 0x04, // format: 4
+        0x00, 0x02, // axis count: 2
+        0x00, 0x00, // flags: 0
+        0x01, 0x04, // value name ID: 260
+        0x00, 0x00, // axis index [0]: 0
+        0x02, 0xBC, 0x00, 0x00, // value [0]: 700
+        0x00, 0x01, // axis index [1]: 1
+        0x00, 0x01, 0x00, 0x00, // value [1]: 1
+    ];
+
+    #[test]
+    fn axis_values() {
+        let names = crate::name::parse(NAME_DATA).unwrap();
+        let table = Table::parse(STAT_DATA).unwrap();
+        let mut values = table.axis_values(names);
+        let wght = Tag::from_bytes(b"wght");
+        let ital = Tag::from_bytes(b"ital");
+
+        let value = values.next().unwrap();
+        assert_eq!(value.name.unwrap().to_string(), Some("Bold".into()));
+        assert_eq!(value.values().collect::<std::vec::Vec<_>>(), vec![(wght, 700.0)]);
+        assert!(!value.is_elidable());
+
+        let value = values.next().unwrap();
+        assert_eq!(value.name.unwrap().to_string(), Some("Italic".into()));
+        assert_eq!(value.values().collect::<std::vec::Vec<_>>(), vec![(ital, 1.0)]);
+        assert!(value.is_elidable());
+
+        let value = values.next().unwrap();
+        assert_eq!(value.value_name_id, 260);
+        assert!(value.name.is_none());
+        assert_eq!(value.values().collect::<std::vec::Vec<_>>(),
+                   vec![(wght, 700.0), (ital, 1.0)]);
+
+        assert!(values.next().is_none());
+    }
+}
@@ -1,8 +1,11 @@
-//! Implementation of Item Variation Store
+//! Implementation of Item Variation Store and Delta-Set Index Mapping
 //!
 //! https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#item-variation-store
+//! https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#associating-target-items-to-variation-data
 
 use crate::NormalizedCoordinate;
+use core::convert::TryFrom;
+
 use crate::parser::{Stream, FromData, LazyArray16, NumFrom};
 
 
@@ -74,6 +77,27 @@ impl<'a> ItemVariationStore<'a> {
         inner_index: u16,
         coordinates: &[NormalizedCoordinate],
     ) -> Option<f32> {
+        self.parse_delta_impl(outer_index, inner_index, |idx| {
+            self.regions.evaluate_region(idx, coordinates)
+        })
+    }
+
+    /// Same as `parse_delta`, but uses precomputed region scalars when possible.
+    pub fn parse_delta_cached(
+        &self,
+        outer_index: u16,
+        inner_index: u16,
+        coordinates: &[NormalizedCoordinate],
+        scalars: &RegionScalars,
+    ) -> Option<f32> {
+        self.parse_delta_impl(outer_index, inner_index, |idx| {
+            self.regions.evaluate_region_cached(idx, coordinates, scalars)
+        })
+    }
+
+    fn parse_delta_impl<F>(&self, outer_index: u16, inner_index: u16, scalar: F) -> Option<f32>
+        where F: Fn(u16) -> f32
+    {
         let offset = self.data_offsets.get(outer_index)?;
         let mut s = Stream::new_at(self.data, usize::num_from(offset))?;
         let item_count: u16 = s.read()?;
@@ -92,13 +116,13 @@ impl<'a> ItemVariationStore<'a> {
         let mut i = 0;
         while i < short_delta_count {
             let idx = region_indices.get(i)?;
-            delta += f32::from(s.read::<i16>()?) * self.regions.evaluate_region(idx, coordinates);
+            delta += f32::from(s.read::<i16>()?) * scalar(idx);
             i += 1;
         }
 
         while i < region_index_count {
             let idx = region_indices.get(i)?;
-            delta += f32::from(s.read::<i8>()?) * self.regions.evaluate_region(idx, coordinates);
+            delta += f32::from(s.read::<i8>()?) * scalar(idx);
             i += 1;
         }
 
@@ -114,6 +138,25 @@ pub struct VariationRegionList<'a> {
 }
 
 impl<'a> VariationRegionList<'a> {
+    #[inline]
+    pub(crate) fn region_count(&self) -> u16 {
+        self.regions.len().checked_div(self.axis_count).unwrap_or(0)
+    }
+
+    /// Same as `evaluate_region`, but uses a precomputed scalar when possible.
+    #[inline]
+    pub(crate) fn evaluate_region_cached(
+        &self,
+        index: u16,
+        coordinates: &[NormalizedCoordinate],
+        scalars: &RegionScalars,
+    ) -> f32 {
+        match scalars.get(index) {
+            Some(v) => v,
+            None => self.evaluate_region(index, coordinates),
+        }
+    }
+
     #[inline]
     pub(crate) fn evaluate_region(
         &self,
@@ -140,6 +183,54 @@ impl<'a> VariationRegionList<'a> {
 }
 
 
+/// Fonts usually have just a couple of regions, so 64 is more than enough.
+const MAX_CACHED_REGIONS: usize = 64;
+
+/// Precomputed scalars of all regions in a Variation Region List for fixed coordinates.
+#[derive(Clone, Copy)]
+pub(crate) struct RegionScalars {
+    d: [f32; MAX_CACHED_REGIONS], // 256B
+    len: u16,
+}
+
+impl Default for RegionScalars {
+    #[inline]
+    fn default() -> Self {
+        RegionScalars {
+            d: [0.0; MAX_CACHED_REGIONS],
+            len: 0,
+        }
+    }
+}
+
+impl RegionScalars {
+    pub fn new(regions: &VariationRegionList, coordinates: &[NormalizedCoordinate]) -> Self {
+        let mut scalars = RegionScalars::default();
+
+        // When there are too many regions, they will be evaluated on demand.
+        let count = regions.region_count();
+        if usize::from(count) <= MAX_CACHED_REGIONS {
+            for i in 0..count {
+                scalars.d[usize::from(i)] = regions.evaluate_region(i, coordinates);
+            }
+
+            scalars.len = count;
+        }
+
+        scalars
+    }
+
+    #[inline]
+    pub fn get(&self, index: u16) -> Option<f32> {
+        if index < self.len {
+            Some(self.d[usize::from(index)])
+        } else {
+            None
+        }
+    }
+}
+
+
 #[derive(Clone, Copy)]
 struct RegionAxisCoordinatesRecord {
     start_coord: i16,
@@ -191,3 +282,144 @@ impl FromData for RegionAxisCoordinatesRecord {
         })
     }
 }
+
+
+/// A [Delta-Set Index Mapping](
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#associating-target-items-to-variation-data).
+///
+/// Used by `HVAR` and `VVAR` to map an item index to an outer/inner
+/// `ItemVariationStore` index pair.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct DeltaSetIndexMap<'a> {
+    // `None` when the mapping is not present.
+    data: Option<&'a [u8]>,
+}
+
+impl<'a> DeltaSetIndexMap<'a> {
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        DeltaSetIndexMap { data: Some(data) }
+    }
+
+    /// Returns an outer/inner index pair for an item.
+    ///
+    /// When the mapping is not present, the implicit one is used:
+    /// the outer index is the upper 16 bits of `index` and the inner one is the lower 16 bits.
+    pub fn map(&self, index: u32) -> Option<(u16, u16)> {
+        let data = match self.data {
+            Some(data) => data,
+            None => return Some(((index >> 16) as u16, (index & 0xFFFF) as u16)),
+        };
+
+        let mut s = Stream::new(data);
+        let format: u8 = s.read()?;
+        let entry_format: u8 = s.read()?;
+        let map_count = match format {
+            0 => u32::from(s.read::<u16>()?),
+            1 => s.read::<u32>()?,
+            _ => return None,
+        };
+
+        if map_count == 0 {
+            return None;
+        }
+
+        // 'If a given index is greater than mapCount - 1, then the last entry is used.'
+        let index = core::cmp::min(index, map_count - 1);
+
+        let entry_size = ((entry_format >> 4) & 3) + 1;
+        let inner_index_bit_count = u32::from((entry_format & 0xF) + 1);
+
+        s.advance(usize::from(entry_size) * usize::num_from(index));
+
+        let mut n = 0u32;
+        for b in s.read_bytes(usize::from(entry_size))? {
+            n = (n << 8) + u32::from(*b);
+        }
+
+        let outer_index = n >> inner_index_bit_count;
+        let inner_index = n & ((1 << inner_index_bit_count) - 1);
+        Some((
+            u16::try_from(outer_index).ok()?,
+            u16::try_from(inner_index).ok()?
+        ))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_scalars() {
+        let data = &[
+            0x00, 0x00, 0x40, 0x00, 0x40, 0x00, // region [0]: 0, 1, 1
+            0xC0, 0x00, 0xC0, 0x00, 0x00, 0x00, // region [1]: -1, -1, 0
+        ];
+
+        let regions = VariationRegionList { axis_count: 1, regions: LazyArray16::new(data) };
+        assert_eq!(regions.region_count(), 2);
+
+        let coordinates = &[NormalizedCoordinate::from(0.5)];
+        let scalars = RegionScalars::new(&regions, coordinates);
+        assert_eq!(scalars.get(0), Some(0.5));
+        assert_eq!(scalars.get(1), Some(0.0));
+        assert_eq!(scalars.get(2), None);
+        assert_eq!(regions.evaluate_region_cached(0, coordinates, &scalars),
+                   regions.evaluate_region(0, coordinates));
+    }
+
+    #[test]
+    fn delta_set_index_map_format0() {
+        let data = &[
+            0x00, // format: 0
+            0x13, // entry format: 2 bytes per entry, 4 bits for the inner index
+            0x00, 0x03, // map count: 3
+            0x00, 0x12, // entry [0]: outer 1, inner 2
+            0x00, 0x35, // entry [1]: outer 3, inner 5
+            0x01, 0x0F, // entry [2]: outer 16, inner 15
+        ];
+
+        let map = DeltaSetIndexMap::new(data);
+        assert_eq!(map.map(0), Some((1, 2)));
+        assert_eq!(map.map(1), Some((3, 5)));
+        assert_eq!(map.map(2), Some((16, 15)));
+        // The last entry is used for indices outside of the map.
+        assert_eq!(map.map(3), Some((16, 15)));
+        assert_eq!(map.map(0xFFFFFFFF), Some((16, 15)));
+    }
+
+    #[test]
+    fn delta_set_index_map_format1() {
+        let data = &[
+            0x01, // format: 1
+            0x2F, // entry format: 3 bytes per entry, 16 bits for the inner index
+            0x00, 0x00, 0x00, 0x02, // map count: 2
+            0x00, 0x00, 0x07, // entry [0]: outer 0, inner 7
+            0x02, 0x01, 0x00, // entry [1]: outer 2, inner 256
+        ];
+
+        let map = DeltaSetIndexMap::new(data);
+        assert_eq!(map.map(0), Some((0, 7)));
+        assert_eq!(map.map(1), Some((2, 256)));
+        assert_eq!(map.map(2), Some((2, 256)));
+    }
+
+    #[test]
+    fn delta_set_index_map_malformed() {
+        // Unknown format.
+        assert_eq!(DeltaSetIndexMap::new(&[0x02, 0x00, 0x00, 0x01, 0x00]).map(0), None);
+        // Empty map.
+        assert_eq!(DeltaSetIndexMap::new(&[0x00, 0x00, 0x00, 0x00]).map(0), None);
+        // Not enough data.
+        assert_eq!(DeltaSetIndexMap::new(&[0x00, 0x10, 0x00, 0x02, 0x00, 0x00]).map(1), None);
+    }
+
+    #[test]
+    fn delta_set_index_map_identity() {
+        let map = DeltaSetIndexMap::default();
+        assert_eq!(map.map(5), Some((0, 5)));
+        assert_eq!(map.map(0x0003_0010), Some((3, 16)));
+    }
+}
@@ -3,8 +3,12 @@
 #![allow(missing_docs)]
 #![allow(dead_code)]
 
+use std::fmt::Write;
+use std::string::String;
 use std::vec::Vec;
 
+use crate::OutlineBuilder;
+
 #[allow(missing_debug_implementations)]
 #[derive(Clone, Copy)]
 pub enum TtfType {
@@ -108,3 +112,30 @@ impl Writer {
         convert_type(value, &mut self.data);
     }
 }
+
+
+/// An `OutlineBuilder` that records an outline as an SVG-like path string.
+#[allow(missing_debug_implementations)]
+pub struct Builder(pub String);
+
+impl OutlineBuilder for Builder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        write!(&mut self.0, "M {} {} ", x, y).unwrap();
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        write!(&mut self.0, "L {} {} ", x, y).unwrap();
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        write!(&mut self.0, "Q {} {} {} {} ", x1, y1, x, y).unwrap();
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        write!(&mut self.0, "C {} {} {} {} {} {} ", x1, y1, x2, y2, x, y).unwrap();
+    }
+
+    fn close(&mut self) {
+        write!(&mut self.0, "Z ").unwrap();
+    }
+}